@@ -20,6 +20,10 @@ typedef struct pcb_FunctionTypeOpaque pcb_FunctionTypeOpaque;
 
 typedef pcb_FunctionTypeOpaque* pcb_FunctionType;
 
+typedef struct pcb_ConstantOpaque pcb_ConstantOpaque;
+
+typedef pcb_ConstantOpaque* pcb_Constant;
+
 typedef struct pcb_FunctionOpaque pcb_FunctionOpaque;
 
 typedef pcb_FunctionOpaque const* pcb_FunctionRef;
@@ -32,64 +36,385 @@ typedef struct pcb_ValueOpaque pcb_ValueOpaque;
 
 typedef pcb_ValueOpaque const* pcb_ValueRef;
 
+typedef struct pcb_GlobalOpaque pcb_GlobalOpaque;
+
+typedef pcb_GlobalOpaque const* pcb_GlobalRef;
+
 typedef struct pcb_TypeOpaque pcb_TypeOpaque;
 
 typedef pcb_TypeOpaque const* pcb_TypeRef;
 
+typedef struct pcb_SourceFileOpaque pcb_SourceFileOpaque;
+
+typedef pcb_SourceFileOpaque const* pcb_SourceFileRef;
+
+typedef struct pcb_SwitchCase {
+	uint64_t value;
+	pcb_BlockRef block;
+} pcb_SwitchCase;
+
+typedef enum pcb_CallConv {
+	pcb_CallConv_C,
+	pcb_CallConv_Fast,
+	pcb_CallConv_Cold,
+} pcb_CallConv;
+
+typedef enum pcb_AtomicOrdering {
+	pcb_AtomicOrdering_Relaxed,
+	pcb_AtomicOrdering_Acquire,
+	pcb_AtomicOrdering_Release,
+	pcb_AtomicOrdering_AcqRel,
+	pcb_AtomicOrdering_SeqCst,
+} pcb_AtomicOrdering;
+
+typedef enum pcb_AtomicRmwOp {
+	pcb_AtomicRmwOp_Xchg,
+	pcb_AtomicRmwOp_Add,
+	pcb_AtomicRmwOp_Sub,
+	pcb_AtomicRmwOp_And,
+	pcb_AtomicRmwOp_Or,
+	pcb_AtomicRmwOp_Xor,
+	pcb_AtomicRmwOp_SMax,
+	pcb_AtomicRmwOp_SMin,
+	pcb_AtomicRmwOp_UMax,
+	pcb_AtomicRmwOp_UMin,
+} pcb_AtomicRmwOp;
+
+typedef enum pcb_Linkage {
+	pcb_Linkage_External,
+	pcb_Linkage_Internal,
+	pcb_Linkage_Private,
+	pcb_Linkage_Weak,
+	pcb_Linkage_LinkOnceOdr,
+	pcb_Linkage_Common,
+} pcb_Linkage;
+
+typedef enum pcb_Visibility {
+	pcb_Visibility_Default,
+	pcb_Visibility_Hidden,
+	pcb_Visibility_Protected,
+} pcb_Visibility;
+
 pcb_Ctxt pcb_ctxt(void);
 
 void pcb_delete_ctxt(pcb_Ctxt ctxt);
 
 void pcb_print_ctxt(pcb_Ctxt const* ctxt);
 
+void pcb_set_debug_info(pcb_Ctxt ctxt, bool enabled);
+
 pcb_FunctionType pcb_function_type(pcb_TypeRef const* inputs, size_t inputs_len, pcb_TypeRef output);
 
+pcb_FunctionType pcb_variadic_function_type(pcb_TypeRef const* inputs, size_t inputs_len, pcb_TypeRef output);
+
 pcb_FunctionType pcb_clone_function_type(pcb_FunctionType const* ty);
 
+void pcb_set_function_type_call_conv(pcb_FunctionType ty, pcb_CallConv call_conv);
+
 void pcb_delete_function_type(pcb_FunctionType func);
 
 pcb_FunctionRef pcb_add_function(pcb_Ctxt const* ctxt, char const* name, size_t name_len, pcb_FunctionType ty);
 
+pcb_FunctionRef pcb_declare_function(pcb_Ctxt const* ctxt, char const* name, size_t name_len, pcb_FunctionType ty);
+
 pcb_ValueRef pcb_get_argument(pcb_FunctionRef func, uint32_t number);
 
+void pcb_set_function_linkage(pcb_FunctionRef func, pcb_Linkage linkage);
+
+void pcb_set_function_visibility(pcb_FunctionRef func, pcb_Visibility visibility);
+
+void pcb_set_function_personality(pcb_FunctionRef func, pcb_FunctionRef personality);
+
+void pcb_set_function_debug_info(pcb_FunctionRef func, char const* name, size_t name_len, pcb_SourceFileRef file, uint32_t line, char const* const* param_names, size_t const* param_name_lens, size_t params_len);
+
+pcb_Constant pcb_constant_int(pcb_TypeRef ty, uint64_t value);
+
+pcb_Constant pcb_constant_float(pcb_TypeRef ty, double value);
+
+pcb_Constant pcb_constant_bool(pcb_Ctxt const* ctxt, bool value);
+
+pcb_Constant pcb_constant_aggregate(pcb_TypeRef ty, pcb_Constant const* fields, size_t fields_len);
+
+pcb_Constant pcb_constant_array(pcb_TypeRef ty, pcb_Constant const* elements, size_t elements_len);
+
+pcb_Constant pcb_constant_bytes(pcb_Ctxt const* ctxt, char const* bytes, size_t bytes_len);
+
+pcb_Constant pcb_constant_global_address(pcb_Ctxt const* ctxt, pcb_GlobalRef global);
+
+pcb_Constant pcb_constant_function_address(pcb_Ctxt const* ctxt, pcb_FunctionRef func);
+
+void pcb_delete_constant(pcb_Constant constant);
+
+pcb_GlobalRef pcb_add_global(pcb_Ctxt const* ctxt, char const* name, size_t name_len, pcb_TypeRef ty, pcb_Constant init);
+
+pcb_GlobalRef pcb_add_constant_global(pcb_Ctxt const* ctxt, char const* name, size_t name_len, pcb_Constant init);
+
+void pcb_set_global_linkage(pcb_GlobalRef global, pcb_Linkage linkage);
+
+void pcb_set_global_visibility(pcb_GlobalRef global, pcb_Visibility visibility);
+
+pcb_SourceFileRef pcb_add_source_file(pcb_Ctxt const* ctxt, char const* name, size_t name_len, char const* directory, size_t directory_len);
+
 pcb_BlockRef pcb_append_block(pcb_FunctionRef func);
 
+pcb_BlockRef pcb_append_block_with_params(pcb_FunctionRef func, pcb_TypeRef const* params, size_t params_len);
+
+void pcb_set_block_name(pcb_BlockRef blk, char const* name, size_t name_len);
+
+pcb_ValueRef pcb_get_block_parameter(pcb_BlockRef blk, uint32_t number);
+
+pcb_BlockRef pcb_append_landing_pad(pcb_FunctionRef func, bool cleanup, pcb_GlobalRef const* catches, size_t catches_len);
+
+pcb_ValueRef pcb_get_exception(pcb_BlockRef blk);
+
+void pcb_set_location(pcb_BlockRef blk, pcb_SourceFileRef file, uint32_t line, uint32_t column);
+
+void pcb_clear_location(pcb_BlockRef blk);
+
 pcb_ValueRef pcb_build_const_int(pcb_BlockRef blk, pcb_TypeRef ty, uint64_t value);
 
+pcb_ValueRef pcb_build_const_float(pcb_BlockRef blk, pcb_TypeRef ty, double value);
+
+pcb_ValueRef pcb_build_const_bool(pcb_BlockRef blk, bool value);
+
+pcb_ValueRef pcb_build_const_aggregate(pcb_BlockRef blk, pcb_TypeRef ty, pcb_ValueRef const* fields, size_t fields_len);
+
+pcb_ValueRef pcb_build_const_array(pcb_BlockRef blk, pcb_TypeRef ty, pcb_ValueRef const* elements, size_t elements_len);
+
 pcb_ValueRef pcb_build_call(pcb_BlockRef blk, pcb_FunctionRef func, pcb_ValueRef const* args, size_t args_len);
 
+pcb_ValueRef pcb_build_tail_call(pcb_BlockRef blk, pcb_FunctionRef func, pcb_ValueRef const* args, size_t args_len);
+
+pcb_ValueRef pcb_build_call_indirect(pcb_BlockRef blk, pcb_ValueRef callee, pcb_ValueRef const* args, size_t args_len);
+
+pcb_ValueRef pcb_build_tail_call_indirect(pcb_BlockRef blk, pcb_ValueRef callee, pcb_ValueRef const* args, size_t args_len);
+
+pcb_ValueRef pcb_build_inline_asm(pcb_BlockRef blk, char const* asm_template, size_t asm_template_len, char const* constraints, size_t constraints_len, bool side_effects, bool align_stack, pcb_TypeRef ty, pcb_ValueRef const* operands, size_t operands_len);
+
+pcb_ValueRef pcb_build_function_address(pcb_BlockRef blk, pcb_FunctionRef func);
+
+pcb_ValueRef pcb_build_global_address(pcb_BlockRef blk, pcb_GlobalRef global);
+
+pcb_ValueRef pcb_build_extract_field(pcb_BlockRef blk, pcb_ValueRef value, uint32_t index);
+
+pcb_ValueRef pcb_build_insert_field(pcb_BlockRef blk, pcb_ValueRef value, uint32_t index, pcb_ValueRef field);
+
+pcb_ValueRef pcb_build_alloca(pcb_BlockRef blk, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_load(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_TypeRef ty);
+
+void pcb_build_store(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_ValueRef value);
+
+pcb_ValueRef pcb_build_field_address(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_TypeRef ty, uint32_t index);
+
+pcb_ValueRef pcb_build_element_address(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_TypeRef ty, pcb_ValueRef index);
+
+pcb_ValueRef pcb_build_atomic_load(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_TypeRef ty, pcb_AtomicOrdering ordering);
+
+void pcb_build_atomic_store(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_ValueRef value, pcb_AtomicOrdering ordering);
+
+pcb_ValueRef pcb_build_atomic_rmw(pcb_BlockRef blk, pcb_AtomicRmwOp op, pcb_ValueRef ptr, pcb_ValueRef value, pcb_AtomicOrdering ordering);
+
+pcb_ValueRef pcb_build_cmpxchg(pcb_BlockRef blk, pcb_ValueRef ptr, pcb_ValueRef expected, pcb_ValueRef desired, pcb_AtomicOrdering success, pcb_AtomicOrdering failure);
+
+void pcb_build_fence(pcb_BlockRef blk, pcb_AtomicOrdering ordering);
+
+pcb_ValueRef pcb_build_va_start(pcb_BlockRef blk);
+
+pcb_ValueRef pcb_build_va_arg(pcb_BlockRef blk, pcb_ValueRef list, pcb_TypeRef ty);
+
+void pcb_build_va_end(pcb_BlockRef blk, pcb_ValueRef list);
+
+pcb_ValueRef pcb_build_neg(pcb_BlockRef blk, pcb_ValueRef inner);
+
+pcb_ValueRef pcb_build_not(pcb_BlockRef blk, pcb_ValueRef inner);
+
 pcb_ValueRef pcb_build_mul(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_mul_with_flags(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs, bool nsw, bool nuw);
+
 pcb_ValueRef pcb_build_udiv(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_udiv_exact(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
 pcb_ValueRef pcb_build_sdiv(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_sdiv_exact(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
 pcb_ValueRef pcb_build_urem(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
 pcb_ValueRef pcb_build_srem(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
 pcb_ValueRef pcb_build_add(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_add_with_flags(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs, bool nsw, bool nuw);
+
 pcb_ValueRef pcb_build_sub(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_sub_with_flags(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs, bool nsw, bool nuw);
+
 pcb_ValueRef pcb_build_shl(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_shl_with_flags(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs, bool nsw, bool nuw);
+
 pcb_ValueRef pcb_build_zshr(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_zshr_exact(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
 pcb_ValueRef pcb_build_sshr(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
+pcb_ValueRef pcb_build_sshr_exact(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
 pcb_ValueRef pcb_build_and(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
 pcb_ValueRef pcb_build_xor(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
 pcb_ValueRef pcb_build_or(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
 
-void pcb_build_branch(pcb_BlockRef blk, pcb_BlockRef to);
+pcb_ValueRef pcb_build_sadd_overflow(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_uadd_overflow(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ssub_overflow(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_usub_overflow(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_smul_overflow(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_umul_overflow(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_sadd_sat(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_uadd_sat(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ssub_sat(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_usub_sat(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ctpop(pcb_BlockRef blk, pcb_ValueRef inner);
+
+pcb_ValueRef pcb_build_ctlz(pcb_BlockRef blk, pcb_ValueRef inner);
+
+pcb_ValueRef pcb_build_cttz(pcb_BlockRef blk, pcb_ValueRef inner);
+
+pcb_ValueRef pcb_build_bswap(pcb_BlockRef blk, pcb_ValueRef inner);
+
+pcb_ValueRef pcb_build_bitreverse(pcb_BlockRef blk, pcb_ValueRef inner);
+
+pcb_ValueRef pcb_build_fshl(pcb_BlockRef blk, pcb_ValueRef hi, pcb_ValueRef lo, pcb_ValueRef amount);
+
+pcb_ValueRef pcb_build_fshr(pcb_BlockRef blk, pcb_ValueRef hi, pcb_ValueRef lo, pcb_ValueRef amount);
+
+pcb_ValueRef pcb_build_rotl(pcb_BlockRef blk, pcb_ValueRef value, pcb_ValueRef amount);
+
+pcb_ValueRef pcb_build_rotr(pcb_BlockRef blk, pcb_ValueRef value, pcb_ValueRef amount);
+
+pcb_ValueRef pcb_build_eq(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_neq(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ult(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_slt(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ugt(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_sgt(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ulte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_slte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ugte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_sgte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fadd(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fsub(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fmul(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fdiv(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_frem(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_foeq(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_foneq(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_folt(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fogt(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_folte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fogte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fueq(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_funeq(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fult(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fugt(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fulte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_fugte(pcb_BlockRef blk, pcb_ValueRef lhs, pcb_ValueRef rhs);
+
+pcb_ValueRef pcb_build_ftoui(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_ftosi(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_uitof(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_sitof(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_trunc(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_zext(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_sext(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_bitcast(pcb_BlockRef blk, pcb_ValueRef value, pcb_TypeRef ty);
+
+pcb_ValueRef pcb_build_select(pcb_BlockRef blk, pcb_ValueRef cond, pcb_ValueRef then, pcb_ValueRef else_);
+
+void pcb_build_branch(pcb_BlockRef blk, pcb_BlockRef to, pcb_ValueRef const* args, size_t args_len);
+
+void pcb_build_cond_branch(pcb_BlockRef blk, pcb_ValueRef cond, pcb_BlockRef then, pcb_BlockRef else_);
+
+void pcb_build_switch(pcb_BlockRef blk, pcb_ValueRef value, pcb_BlockRef default_, pcb_SwitchCase const* cases, size_t cases_len);
 
 void pcb_build_return(pcb_BlockRef blk, pcb_ValueRef val);
 
+void pcb_build_return_void(pcb_BlockRef blk);
+
+void pcb_build_unreachable(pcb_BlockRef blk);
+
+void pcb_build_invoke(pcb_BlockRef blk, pcb_FunctionRef func, pcb_ValueRef const* args, size_t args_len, pcb_BlockRef normal, pcb_BlockRef unwind);
+
+void pcb_build_resume(pcb_BlockRef blk, pcb_ValueRef exception);
+
+void pcb_set_value_name(pcb_ValueRef val, char const* name, size_t name_len);
+
 pcb_TypeRef pcb_int_type(pcb_Ctxt const* ctxt, uint32_t size);
 
+pcb_TypeRef pcb_float_type(pcb_Ctxt const* ctxt, uint32_t size);
+
+pcb_TypeRef pcb_bool_type(pcb_Ctxt const* ctxt);
+
+pcb_TypeRef pcb_void_type(pcb_Ctxt const* ctxt);
+
+pcb_TypeRef pcb_pointer_type(pcb_Ctxt const* ctxt);
+
+pcb_TypeRef pcb_function_pointer_type(pcb_Ctxt const* ctxt, pcb_FunctionType const* ty);
+
+pcb_TypeRef pcb_aggregate_type(pcb_Ctxt const* ctxt, pcb_TypeRef const* fields, size_t fields_len);
+
+pcb_TypeRef pcb_array_type(pcb_Ctxt const* ctxt, pcb_TypeRef element, uint64_t len);
+
 void pcb_llvm_build_and_write(pcb_Ctxt ctxt, char const* name, uintptr_t name_len, bool print_llvm_ir);
 
 
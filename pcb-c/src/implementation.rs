@@ -1,6 +1,10 @@
 use super::{pcb_FunctionOpaque, pcb_FunctionRef, pcb_BlockOpaque, pcb_BlockRef,
-  pcb_TypeOpaque, pcb_TypeRef, pcb_ValueOpaque, pcb_ValueRef, Wrap, Unwrap};
-use pcb::{ty, Function, Block, Value};
+  pcb_TypeOpaque, pcb_TypeRef, pcb_ValueOpaque, pcb_ValueRef, pcb_GlobalOpaque,
+  pcb_GlobalRef, pcb_AtomicOrdering, pcb_AtomicRmwOp, pcb_CallConv,
+  pcb_Linkage, pcb_Visibility, pcb_SourceFileOpaque, pcb_SourceFileRef, Wrap,
+  Unwrap};
+use pcb::{ty, Function, Block, Value, Global, SourceFile, AtomicOrdering,
+  AtomicRmwOp, Linkage, Visibility};
 
 use std::mem::transmute;
 
@@ -43,6 +47,32 @@ impl<'c> Unwrap<'c> for pcb_ValueOpaque {
   }
 }
 
+impl<'c> Wrap for Global<'c> {
+  type Wrapped = pcb_GlobalOpaque;
+  fn wrap(u: Self) -> pcb_GlobalRef {
+    unsafe { transmute(u) }
+  }
+}
+impl<'c> Unwrap<'c> for pcb_GlobalOpaque {
+  type Unwrapped = Global<'c>;
+  unsafe fn unwrap(w: pcb_GlobalRef) -> Global<'c> {
+    transmute(w)
+  }
+}
+
+impl<'c> Wrap for SourceFile<'c> {
+  type Wrapped = pcb_SourceFileOpaque;
+  fn wrap(u: Self) -> pcb_SourceFileRef {
+    unsafe { transmute(u) }
+  }
+}
+impl<'c> Unwrap<'c> for pcb_SourceFileOpaque {
+  type Unwrapped = SourceFile<'c>;
+  unsafe fn unwrap(w: pcb_SourceFileRef) -> SourceFile<'c> {
+    transmute(w)
+  }
+}
+
 impl<'c> Wrap for ty::Type<'c> {
   type Wrapped = pcb_TypeOpaque;
   fn wrap(u: Self) -> pcb_TypeRef {
@@ -55,3 +85,65 @@ impl<'c> Unwrap<'c> for pcb_TypeOpaque {
     transmute(w)
   }
 }
+
+impl pcb_AtomicOrdering {
+  pub fn into_ordering(self) -> AtomicOrdering {
+    match self {
+      pcb_AtomicOrdering::pcb_AtomicOrdering_Relaxed => AtomicOrdering::Relaxed,
+      pcb_AtomicOrdering::pcb_AtomicOrdering_Acquire => AtomicOrdering::Acquire,
+      pcb_AtomicOrdering::pcb_AtomicOrdering_Release => AtomicOrdering::Release,
+      pcb_AtomicOrdering::pcb_AtomicOrdering_AcqRel => AtomicOrdering::AcqRel,
+      pcb_AtomicOrdering::pcb_AtomicOrdering_SeqCst => AtomicOrdering::SeqCst,
+    }
+  }
+}
+
+impl pcb_AtomicRmwOp {
+  pub fn into_op(self) -> AtomicRmwOp {
+    match self {
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_Xchg => AtomicRmwOp::Xchg,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_Add => AtomicRmwOp::Add,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_Sub => AtomicRmwOp::Sub,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_And => AtomicRmwOp::And,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_Or => AtomicRmwOp::Or,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_Xor => AtomicRmwOp::Xor,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_SMax => AtomicRmwOp::SMax,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_SMin => AtomicRmwOp::SMin,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_UMax => AtomicRmwOp::UMax,
+      pcb_AtomicRmwOp::pcb_AtomicRmwOp_UMin => AtomicRmwOp::UMin,
+    }
+  }
+}
+
+impl pcb_CallConv {
+  pub fn into_call_conv(self) -> ty::CallConv {
+    match self {
+      pcb_CallConv::pcb_CallConv_C => ty::CallConv::C,
+      pcb_CallConv::pcb_CallConv_Fast => ty::CallConv::Fast,
+      pcb_CallConv::pcb_CallConv_Cold => ty::CallConv::Cold,
+    }
+  }
+}
+
+impl pcb_Linkage {
+  pub fn into_linkage(self) -> Linkage {
+    match self {
+      pcb_Linkage::pcb_Linkage_External => Linkage::External,
+      pcb_Linkage::pcb_Linkage_Internal => Linkage::Internal,
+      pcb_Linkage::pcb_Linkage_Private => Linkage::Private,
+      pcb_Linkage::pcb_Linkage_Weak => Linkage::Weak,
+      pcb_Linkage::pcb_Linkage_LinkOnceOdr => Linkage::LinkOnceOdr,
+      pcb_Linkage::pcb_Linkage_Common => Linkage::Common,
+    }
+  }
+}
+
+impl pcb_Visibility {
+  pub fn into_visibility(self) -> Visibility {
+    match self {
+      pcb_Visibility::pcb_Visibility_Default => Visibility::Default,
+      pcb_Visibility::pcb_Visibility_Hidden => Visibility::Hidden,
+      pcb_Visibility::pcb_Visibility_Protected => Visibility::Protected,
+    }
+  }
+}
@@ -6,7 +6,8 @@ extern crate pcb;
 extern crate pcb_llvm;
 extern crate libc;
 
-use pcb::{ty, Ctxt, Function, Block};
+use pcb::{ty, Ctxt, Function, Block, Global, Constant, SourceFile,
+  WrapFlags};
 
 mod implementation;
 
@@ -17,6 +18,9 @@ pub type pcb_Ctxt = *mut pcb_CtxtOpaque;
 #[repr(C)]
 pub struct pcb_FunctionTypeOpaque(ty::Function<'static>);
 pub type pcb_FunctionType = *mut pcb_FunctionTypeOpaque;
+#[repr(C)]
+pub struct pcb_ConstantOpaque(Constant<'static>);
+pub type pcb_Constant = *mut pcb_ConstantOpaque;
 
 // Do not need to be destroyed
 #[repr(C)]
@@ -29,8 +33,72 @@ pub type pcb_BlockRef = *const pcb_BlockOpaque;
 pub struct pcb_ValueOpaque(());
 pub type pcb_ValueRef = *const pcb_ValueOpaque;
 #[repr(C)]
+pub struct pcb_GlobalOpaque(());
+pub type pcb_GlobalRef = *const pcb_GlobalOpaque;
+#[repr(C)]
 pub struct pcb_TypeOpaque(());
 pub type pcb_TypeRef = *const pcb_TypeOpaque;
+#[repr(C)]
+pub struct pcb_SourceFileOpaque(());
+pub type pcb_SourceFileRef = *const pcb_SourceFileOpaque;
+
+#[repr(C)]
+pub struct pcb_SwitchCase {
+  value: u64,
+  block: pcb_BlockRef,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum pcb_CallConv {
+  pcb_CallConv_C,
+  pcb_CallConv_Fast,
+  pcb_CallConv_Cold,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum pcb_AtomicOrdering {
+  pcb_AtomicOrdering_Relaxed,
+  pcb_AtomicOrdering_Acquire,
+  pcb_AtomicOrdering_Release,
+  pcb_AtomicOrdering_AcqRel,
+  pcb_AtomicOrdering_SeqCst,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum pcb_AtomicRmwOp {
+  pcb_AtomicRmwOp_Xchg,
+  pcb_AtomicRmwOp_Add,
+  pcb_AtomicRmwOp_Sub,
+  pcb_AtomicRmwOp_And,
+  pcb_AtomicRmwOp_Or,
+  pcb_AtomicRmwOp_Xor,
+  pcb_AtomicRmwOp_SMax,
+  pcb_AtomicRmwOp_SMin,
+  pcb_AtomicRmwOp_UMax,
+  pcb_AtomicRmwOp_UMin,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum pcb_Linkage {
+  pcb_Linkage_External,
+  pcb_Linkage_Internal,
+  pcb_Linkage_Private,
+  pcb_Linkage_Weak,
+  pcb_Linkage_LinkOnceOdr,
+  pcb_Linkage_Common,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum pcb_Visibility {
+  pcb_Visibility_Default,
+  pcb_Visibility_Hidden,
+  pcb_Visibility_Protected,
+}
 
 // == pcb_Ctxt ==
 
@@ -46,13 +114,32 @@ pub unsafe extern fn pcb_delete_ctxt(ctxt: pcb_Ctxt) {
 pub unsafe extern fn pcb_print_ctxt(ctxt: *const pcb_Ctxt) {
   println!("{}", (**ctxt).0);
 }
+#[no_mangle]
+pub unsafe extern fn pcb_set_debug_info(ctxt: pcb_Ctxt, enabled: bool) {
+  (*ctxt).0.set_debug_info(enabled)
+}
 
 // == pcb_FunctionType ==
 
 #[no_mangle]
-pub unsafe extern fn pcb_function_type(mut inputs: *const pcb_TypeRef,
+pub unsafe extern fn pcb_function_type(inputs: *const pcb_TypeRef,
     inputs_len: libc::size_t, output: pcb_TypeRef) -> pcb_FunctionType {
-  let inputs = if inputs_len == 0 {
+  Box::into_raw(Box::new(pcb_FunctionTypeOpaque(
+      ty::Function::new(function_type_inputs(inputs, inputs_len),
+        unwrap(output)))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_variadic_function_type(inputs: *const pcb_TypeRef,
+    inputs_len: libc::size_t, output: pcb_TypeRef) -> pcb_FunctionType {
+  Box::into_raw(Box::new(pcb_FunctionTypeOpaque(
+      ty::Function::new_variadic(function_type_inputs(inputs, inputs_len),
+        unwrap(output)))))
+}
+
+unsafe fn function_type_inputs<'c>(mut inputs: *const pcb_TypeRef,
+    inputs_len: libc::size_t) -> Vec<ty::Type<'c>> {
+  if inputs_len == 0 {
     vec![]
   } else {
     let mut v = vec![];
@@ -62,9 +149,7 @@ pub unsafe extern fn pcb_function_type(mut inputs: *const pcb_TypeRef,
       inputs = inputs.offset(1);
     }
     v
-  };
-  Box::into_raw(Box::new(pcb_FunctionTypeOpaque(
-      ty::Function::new(inputs, unwrap(output)))))
+  }
 }
 
 #[no_mangle]
@@ -74,6 +159,12 @@ pub unsafe extern fn pcb_clone_function_type(ty: *const pcb_FunctionType)
   Box::into_raw(Box::new(pcb_FunctionTypeOpaque(ref_.clone())))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_set_function_type_call_conv(ty: pcb_FunctionType,
+    call_conv: pcb_CallConv) {
+  (*ty).0.set_call_conv(call_conv.into_call_conv())
+}
+
 #[no_mangle]
 pub unsafe extern fn pcb_delete_function_type(func: pcb_FunctionType) {
   Box::from_raw(func);
@@ -89,12 +180,179 @@ pub unsafe extern fn pcb_add_function(ctxt: *const pcb_Ctxt,
   wrap(Function::new(&(**ctxt).0, name, Box::from_raw(ty).0))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_declare_function(ctxt: *const pcb_Ctxt,
+    name: *const libc::c_char, name_len: libc::size_t, ty: pcb_FunctionType)
+    -> pcb_FunctionRef {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  wrap(Function::declare(&(**ctxt).0, name, Box::from_raw(ty).0))
+}
+
 #[no_mangle]
 pub unsafe extern fn pcb_get_argument(func: pcb_FunctionRef,
     number: u32) -> pcb_ValueRef {
   wrap(unwrap(func).get_argument(number))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_set_function_linkage(func: pcb_FunctionRef,
+    linkage: pcb_Linkage) {
+  unwrap(func).set_linkage(linkage.into_linkage())
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_function_visibility(func: pcb_FunctionRef,
+    visibility: pcb_Visibility) {
+  unwrap(func).set_visibility(visibility.into_visibility())
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_function_personality(func: pcb_FunctionRef,
+    personality: pcb_FunctionRef) {
+  unwrap(func).set_personality(unwrap(personality))
+}
+
+// `param_names` and `param_name_lens` are both `params_len` long
+#[no_mangle]
+pub unsafe extern fn pcb_set_function_debug_info(func: pcb_FunctionRef,
+    name: *const libc::c_char, name_len: libc::size_t, file: pcb_SourceFileRef,
+    line: u32, param_names: *const *const libc::c_char,
+    param_name_lens: *const libc::size_t, params_len: libc::size_t) {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  let names = ptr_len_to_slice(param_names, params_len);
+  let lens = ptr_len_to_slice(param_name_lens, params_len);
+  let mut unwrapped = vec![];
+  for (&name, &len) in names.iter().zip(lens.iter()) {
+    unwrapped.push(ptr_len_to_str(name as *const u8, len));
+  }
+  unwrap(func).set_debug_info(name, unwrap(file), line, &unwrapped)
+}
+
+// == pcb_Constant ==
+
+#[no_mangle]
+pub unsafe extern fn pcb_constant_int(ty: pcb_TypeRef, value: u64)
+    -> pcb_Constant {
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::int(unwrap(ty), value))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_constant_float(ty: pcb_TypeRef, value: f64)
+    -> pcb_Constant {
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::float(unwrap(ty), value))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_constant_bool(ctxt: *const pcb_Ctxt, value: bool)
+    -> pcb_Constant {
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::bool(&(**ctxt).0, value))))
+}
+
+// takes ownership of the fields
+#[no_mangle]
+pub unsafe extern fn pcb_constant_aggregate(ty: pcb_TypeRef,
+    fields: *const pcb_Constant, fields_len: libc::size_t) -> pcb_Constant {
+  let opaque = ptr_len_to_slice(fields, fields_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(Box::from_raw(*el).0);
+  }
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::aggregate(unwrap(ty), unwrapped))))
+}
+
+// takes ownership of the elements
+#[no_mangle]
+pub unsafe extern fn pcb_constant_array(ty: pcb_TypeRef,
+    elements: *const pcb_Constant, elements_len: libc::size_t)
+    -> pcb_Constant {
+  let opaque = ptr_len_to_slice(elements, elements_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(Box::from_raw(*el).0);
+  }
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::array(unwrap(ty), unwrapped))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_constant_bytes(ctxt: *const pcb_Ctxt,
+    bytes: *const libc::c_char, bytes_len: libc::size_t) -> pcb_Constant {
+  let bytes = ptr_len_to_slice(bytes as *const u8, bytes_len);
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::bytes(&(**ctxt).0, bytes))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_constant_global_address(ctxt: *const pcb_Ctxt,
+    global: pcb_GlobalRef) -> pcb_Constant {
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::global_address(&(**ctxt).0, unwrap(global)))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_constant_function_address(ctxt: *const pcb_Ctxt,
+    func: pcb_FunctionRef) -> pcb_Constant {
+  Box::into_raw(Box::new(pcb_ConstantOpaque(
+      Constant::function_address(&(**ctxt).0, unwrap(func)))))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_delete_constant(constant: pcb_Constant) {
+  Box::from_raw(constant);
+}
+
+// == pcb_GlobalRef ==
+
+// `init` may be null, in which case the global is zero-initialized
+#[no_mangle]
+pub unsafe extern fn pcb_add_global(ctxt: *const pcb_Ctxt,
+    name: *const libc::c_char, name_len: libc::size_t, ty: pcb_TypeRef,
+    init: pcb_Constant) -> pcb_GlobalRef {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  let init = if init.is_null() {
+    None
+  } else {
+    Some(Box::from_raw(init).0)
+  };
+  wrap(Global::new(&(**ctxt).0, name, unwrap(ty), init))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_add_constant_global(ctxt: *const pcb_Ctxt,
+    name: *const libc::c_char, name_len: libc::size_t, init: pcb_Constant)
+    -> pcb_GlobalRef {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  wrap(Global::new_constant(&(**ctxt).0, name, Box::from_raw(init).0))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_global_linkage(global: pcb_GlobalRef,
+    linkage: pcb_Linkage) {
+  unwrap(global).set_linkage(linkage.into_linkage())
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_global_visibility(global: pcb_GlobalRef,
+    visibility: pcb_Visibility) {
+  unwrap(global).set_visibility(visibility.into_visibility())
+}
+
+// == pcb_SourceFileRef ==
+
+#[no_mangle]
+pub unsafe extern fn pcb_add_source_file(ctxt: *const pcb_Ctxt,
+    name: *const libc::c_char, name_len: libc::size_t,
+    directory: *const libc::c_char, directory_len: libc::size_t)
+    -> pcb_SourceFileRef {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  let directory = ptr_len_to_str(directory as *const u8, directory_len);
+  wrap(SourceFile::new(&(**ctxt).0, name, directory))
+}
+
 // == pcb_BlockRef ==
 
 #[no_mangle]
@@ -102,6 +360,63 @@ pub unsafe extern fn pcb_append_block(func: pcb_FunctionRef) -> pcb_BlockRef {
   wrap(Block::append(unwrap(func)))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_append_block_with_params(func: pcb_FunctionRef,
+    params: *const pcb_TypeRef, params_len: libc::size_t) -> pcb_BlockRef {
+  let opaque = ptr_len_to_slice(params, params_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(Block::append_with_params(unwrap(func), &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_block_name(blk: pcb_BlockRef,
+    name: *const libc::c_char, name_len: libc::size_t) {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  unwrap(blk).set_name(name)
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_get_block_parameter(blk: pcb_BlockRef,
+    number: u32) -> pcb_ValueRef {
+  wrap(unwrap(blk).get_parameter(number))
+}
+
+// a null catch catches every exception
+#[no_mangle]
+pub unsafe extern fn pcb_append_landing_pad(func: pcb_FunctionRef,
+    cleanup: bool, catches: *const pcb_GlobalRef, catches_len: libc::size_t)
+    -> pcb_BlockRef {
+  let opaque = ptr_len_to_slice(catches, catches_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    if el.is_null() {
+      unwrapped.push(None);
+    } else {
+      unwrapped.push(Some(unwrap(*el)));
+    }
+  }
+  wrap(Block::append_landing_pad(unwrap(func), cleanup, &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_get_exception(blk: pcb_BlockRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).get_exception())
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_location(blk: pcb_BlockRef,
+    file: pcb_SourceFileRef, line: u32, column: u32) {
+  unwrap(blk).set_location(unwrap(file), line, column)
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_clear_location(blk: pcb_BlockRef) {
+  unwrap(blk).clear_location()
+}
+
 // misc
 #[no_mangle]
 pub unsafe extern fn pcb_build_const_int(blk: pcb_BlockRef,
@@ -109,6 +424,42 @@ pub unsafe extern fn pcb_build_const_int(blk: pcb_BlockRef,
   wrap(unwrap(blk).build_const_int(unwrap(ty), value))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_build_const_float(blk: pcb_BlockRef,
+    ty: pcb_TypeRef, value: f64) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_const_float(unwrap(ty), value))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_const_bool(blk: pcb_BlockRef, value: bool)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_const_bool(value))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_const_aggregate(blk: pcb_BlockRef,
+    ty: pcb_TypeRef, fields: *const pcb_ValueRef, fields_len: libc::size_t)
+    -> pcb_ValueRef {
+  let opaque = ptr_len_to_slice(fields, fields_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(unwrap(blk).build_const_aggregate(unwrap(ty), &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_const_array(blk: pcb_BlockRef,
+    ty: pcb_TypeRef, elements: *const pcb_ValueRef,
+    elements_len: libc::size_t) -> pcb_ValueRef {
+  let opaque = ptr_len_to_slice(elements, elements_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(unwrap(blk).build_const_array(unwrap(ty), &unwrapped))
+}
+
 #[no_mangle]
 pub unsafe extern fn pcb_build_call(blk: pcb_BlockRef,
     func: pcb_FunctionRef, args: *const pcb_ValueRef, args_len: libc::size_t)
@@ -121,6 +472,174 @@ pub unsafe extern fn pcb_build_call(blk: pcb_BlockRef,
   wrap(unwrap(blk).build_call(unwrap(func), &unwrapped))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_build_tail_call(blk: pcb_BlockRef,
+    func: pcb_FunctionRef, args: *const pcb_ValueRef, args_len: libc::size_t)
+    -> pcb_ValueRef {
+  let opaque = ptr_len_to_slice(args, args_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(unwrap(blk).build_tail_call(unwrap(func), &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_call_indirect(blk: pcb_BlockRef,
+    callee: pcb_ValueRef, args: *const pcb_ValueRef, args_len: libc::size_t)
+    -> pcb_ValueRef {
+  let opaque = ptr_len_to_slice(args, args_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(unwrap(blk).build_call_indirect(unwrap(callee), &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_tail_call_indirect(blk: pcb_BlockRef,
+    callee: pcb_ValueRef, args: *const pcb_ValueRef, args_len: libc::size_t)
+    -> pcb_ValueRef {
+  let opaque = ptr_len_to_slice(args, args_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(unwrap(blk).build_tail_call_indirect(unwrap(callee), &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_inline_asm(blk: pcb_BlockRef,
+    asm_template: *const libc::c_char, asm_template_len: libc::size_t,
+    constraints: *const libc::c_char, constraints_len: libc::size_t,
+    side_effects: bool, align_stack: bool, ty: pcb_TypeRef,
+    operands: *const pcb_ValueRef, operands_len: libc::size_t)
+    -> pcb_ValueRef {
+  let asm_template =
+    ptr_len_to_str(asm_template as *const u8, asm_template_len);
+  let constraints = ptr_len_to_str(constraints as *const u8, constraints_len);
+  let opaque = ptr_len_to_slice(operands, operands_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(unwrap(blk).build_inline_asm(asm_template, constraints, side_effects,
+    align_stack, unwrap(ty), &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_function_address(blk: pcb_BlockRef,
+    func: pcb_FunctionRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_function_address(unwrap(func)))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_global_address(blk: pcb_BlockRef,
+    global: pcb_GlobalRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_global_address(unwrap(global)))
+}
+
+// aggregates
+#[no_mangle]
+pub unsafe extern fn pcb_build_extract_field(blk: pcb_BlockRef,
+    value: pcb_ValueRef, index: u32) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_extract_field(unwrap(value), index))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_insert_field(blk: pcb_BlockRef,
+    value: pcb_ValueRef, index: u32, field: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_insert_field(unwrap(value), index, unwrap(field)))
+}
+
+// memory
+#[no_mangle]
+pub unsafe extern fn pcb_build_alloca(blk: pcb_BlockRef, ty: pcb_TypeRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_alloca(unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_load(blk: pcb_BlockRef, ptr: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_load(unwrap(ptr), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_store(blk: pcb_BlockRef, ptr: pcb_ValueRef,
+    value: pcb_ValueRef) {
+  unwrap(blk).build_store(unwrap(ptr), unwrap(value))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_field_address(blk: pcb_BlockRef,
+    ptr: pcb_ValueRef, ty: pcb_TypeRef, index: u32) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_field_address(unwrap(ptr), unwrap(ty), index))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_element_address(blk: pcb_BlockRef,
+    ptr: pcb_ValueRef, ty: pcb_TypeRef, index: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_element_address(unwrap(ptr), unwrap(ty),
+    unwrap(index)))
+}
+
+// atomics
+#[no_mangle]
+pub unsafe extern fn pcb_build_atomic_load(blk: pcb_BlockRef,
+    ptr: pcb_ValueRef, ty: pcb_TypeRef, ordering: pcb_AtomicOrdering)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_atomic_load(unwrap(ptr), unwrap(ty),
+    ordering.into_ordering()))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_atomic_store(blk: pcb_BlockRef,
+    ptr: pcb_ValueRef, value: pcb_ValueRef, ordering: pcb_AtomicOrdering) {
+  unwrap(blk).build_atomic_store(unwrap(ptr), unwrap(value),
+    ordering.into_ordering())
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_atomic_rmw(blk: pcb_BlockRef,
+    op: pcb_AtomicRmwOp, ptr: pcb_ValueRef, value: pcb_ValueRef,
+    ordering: pcb_AtomicOrdering) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_atomic_rmw(op.into_op(), unwrap(ptr), unwrap(value),
+    ordering.into_ordering()))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_cmpxchg(blk: pcb_BlockRef, ptr: pcb_ValueRef,
+    expected: pcb_ValueRef, new: pcb_ValueRef, success: pcb_AtomicOrdering,
+    failure: pcb_AtomicOrdering) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_cmpxchg(unwrap(ptr), unwrap(expected), unwrap(new),
+    success.into_ordering(), failure.into_ordering()))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fence(blk: pcb_BlockRef,
+    ordering: pcb_AtomicOrdering) {
+  unwrap(blk).build_fence(ordering.into_ordering())
+}
+
+// varargs
+#[no_mangle]
+pub unsafe extern fn pcb_build_va_start(blk: pcb_BlockRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_va_start())
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_va_arg(blk: pcb_BlockRef, list: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_va_arg(unwrap(list), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_va_end(blk: pcb_BlockRef, list: pcb_ValueRef) {
+  unwrap(blk).build_va_end(unwrap(list))
+}
+
+// unary ops
+#[no_mangle]
+pub unsafe extern fn pcb_build_neg(blk: pcb_BlockRef, inner: pcb_ValueRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_neg(unwrap(inner)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_not(blk: pcb_BlockRef, inner: pcb_ValueRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_not(unwrap(inner)))
+}
+
 // binops
 #[no_mangle]
 pub unsafe extern fn pcb_build_mul(blk: pcb_BlockRef, lhs: pcb_ValueRef,
@@ -128,16 +647,33 @@ pub unsafe extern fn pcb_build_mul(blk: pcb_BlockRef, lhs: pcb_ValueRef,
   wrap(unwrap(blk).build_mul(unwrap(lhs), unwrap(rhs)))
 }
 #[no_mangle]
+pub unsafe extern fn pcb_build_mul_with_flags(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef, nsw: bool, nuw: bool)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_mul_with_flags(unwrap(lhs), unwrap(rhs),
+    WrapFlags { nsw: nsw, nuw: nuw }))
+}
+#[no_mangle]
 pub unsafe extern fn pcb_build_udiv(blk: pcb_BlockRef, lhs: pcb_ValueRef,
     rhs: pcb_ValueRef) -> pcb_ValueRef {
   wrap(unwrap(blk).build_udiv(unwrap(lhs), unwrap(rhs)))
 }
 #[no_mangle]
+pub unsafe extern fn pcb_build_udiv_exact(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_udiv_exact(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
 pub unsafe extern fn pcb_build_sdiv(blk: pcb_BlockRef, lhs: pcb_ValueRef,
     rhs: pcb_ValueRef) -> pcb_ValueRef {
   wrap(unwrap(blk).build_sdiv(unwrap(lhs), unwrap(rhs)))
 }
 #[no_mangle]
+pub unsafe extern fn pcb_build_sdiv_exact(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sdiv_exact(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
 pub unsafe extern fn pcb_build_urem(blk: pcb_BlockRef, lhs: pcb_ValueRef,
     rhs: pcb_ValueRef) -> pcb_ValueRef {
   wrap(unwrap(blk).build_urem(unwrap(lhs), unwrap(rhs)))
@@ -154,10 +690,24 @@ pub unsafe extern fn pcb_build_add(blk: pcb_BlockRef, lhs: pcb_ValueRef,
   wrap(unwrap(blk).build_add(unwrap(lhs), unwrap(rhs)))
 }
 #[no_mangle]
+pub unsafe extern fn pcb_build_add_with_flags(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef, nsw: bool, nuw: bool)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_add_with_flags(unwrap(lhs), unwrap(rhs),
+    WrapFlags { nsw: nsw, nuw: nuw }))
+}
+#[no_mangle]
 pub unsafe extern fn pcb_build_sub(blk: pcb_BlockRef, lhs: pcb_ValueRef,
     rhs: pcb_ValueRef) -> pcb_ValueRef {
   wrap(unwrap(blk).build_sub(unwrap(lhs), unwrap(rhs)))
 }
+#[no_mangle]
+pub unsafe extern fn pcb_build_sub_with_flags(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef, nsw: bool, nuw: bool)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sub_with_flags(unwrap(lhs), unwrap(rhs),
+    WrapFlags { nsw: nsw, nuw: nuw }))
+}
 
 #[no_mangle]
 pub unsafe extern fn pcb_build_shl(blk: pcb_BlockRef, lhs: pcb_ValueRef,
@@ -165,15 +715,32 @@ pub unsafe extern fn pcb_build_shl(blk: pcb_BlockRef, lhs: pcb_ValueRef,
   wrap(unwrap(blk).build_shl(unwrap(lhs), unwrap(rhs)))
 }
 #[no_mangle]
+pub unsafe extern fn pcb_build_shl_with_flags(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef, nsw: bool, nuw: bool)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_shl_with_flags(unwrap(lhs), unwrap(rhs),
+    WrapFlags { nsw: nsw, nuw: nuw }))
+}
+#[no_mangle]
 pub unsafe extern fn pcb_build_zshr(blk: pcb_BlockRef, lhs: pcb_ValueRef,
     rhs: pcb_ValueRef) -> pcb_ValueRef {
   wrap(unwrap(blk).build_zshr(unwrap(lhs), unwrap(rhs)))
 }
 #[no_mangle]
+pub unsafe extern fn pcb_build_zshr_exact(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_zshr_exact(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
 pub unsafe extern fn pcb_build_sshr(blk: pcb_BlockRef, lhs: pcb_ValueRef,
     rhs: pcb_ValueRef) -> pcb_ValueRef {
   wrap(unwrap(blk).build_sshr(unwrap(lhs), unwrap(rhs)))
 }
+#[no_mangle]
+pub unsafe extern fn pcb_build_sshr_exact(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sshr_exact(unwrap(lhs), unwrap(rhs)))
+}
 
 #[no_mangle]
 pub unsafe extern fn pcb_build_and(blk: pcb_BlockRef, lhs: pcb_ValueRef,
@@ -191,11 +758,330 @@ pub unsafe extern fn pcb_build_or(blk: pcb_BlockRef, lhs: pcb_ValueRef,
   wrap(unwrap(blk).build_or(unwrap(lhs), unwrap(rhs)))
 }
 
+// checked arithmetic
+#[no_mangle]
+pub unsafe extern fn pcb_build_sadd_overflow(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sadd_overflow(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_uadd_overflow(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_uadd_overflow(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_ssub_overflow(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ssub_overflow(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_usub_overflow(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_usub_overflow(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_smul_overflow(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_smul_overflow(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_umul_overflow(blk: pcb_BlockRef,
+    lhs: pcb_ValueRef, rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_umul_overflow(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_sadd_sat(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sadd_sat(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_uadd_sat(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_uadd_sat(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_ssub_sat(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ssub_sat(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_usub_sat(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_usub_sat(unwrap(lhs), unwrap(rhs)))
+}
+
+// bit manipulation
+#[no_mangle]
+pub unsafe extern fn pcb_build_ctpop(blk: pcb_BlockRef, inner: pcb_ValueRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ctpop(unwrap(inner)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_ctlz(blk: pcb_BlockRef, inner: pcb_ValueRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ctlz(unwrap(inner)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_cttz(blk: pcb_BlockRef, inner: pcb_ValueRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_cttz(unwrap(inner)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_bswap(blk: pcb_BlockRef, inner: pcb_ValueRef)
+    -> pcb_ValueRef {
+  wrap(unwrap(blk).build_bswap(unwrap(inner)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_bitreverse(blk: pcb_BlockRef,
+    inner: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_bitreverse(unwrap(inner)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fshl(blk: pcb_BlockRef, hi: pcb_ValueRef,
+    lo: pcb_ValueRef, amount: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fshl(unwrap(hi), unwrap(lo), unwrap(amount)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fshr(blk: pcb_BlockRef, hi: pcb_ValueRef,
+    lo: pcb_ValueRef, amount: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fshr(unwrap(hi), unwrap(lo), unwrap(amount)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_rotl(blk: pcb_BlockRef, value: pcb_ValueRef,
+    amount: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_rotl(unwrap(value), unwrap(amount)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_rotr(blk: pcb_BlockRef, value: pcb_ValueRef,
+    amount: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_rotr(unwrap(value), unwrap(amount)))
+}
+
+// comparisons
+#[no_mangle]
+pub unsafe extern fn pcb_build_eq(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_eq(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_neq(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_neq(unwrap(lhs), unwrap(rhs)))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_ult(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ult(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_slt(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_slt(unwrap(lhs), unwrap(rhs)))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_ugt(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ugt(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_sgt(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sgt(unwrap(lhs), unwrap(rhs)))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_ulte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ulte(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_slte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_slte(unwrap(lhs), unwrap(rhs)))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_ugte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ugte(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_sgte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sgte(unwrap(lhs), unwrap(rhs)))
+}
+
+// float binops
+#[no_mangle]
+pub unsafe extern fn pcb_build_fadd(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fadd(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fsub(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fsub(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fmul(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fmul(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fdiv(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fdiv(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_frem(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_frem(unwrap(lhs), unwrap(rhs)))
+}
+
+// float comparisons
+#[no_mangle]
+pub unsafe extern fn pcb_build_foeq(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_foeq(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_foneq(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_foneq(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_folt(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_folt(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fogt(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fogt(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_folte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_folte(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fogte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fogte(unwrap(lhs), unwrap(rhs)))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_fueq(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fueq(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_funeq(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_funeq(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fult(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fult(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fugt(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fugt(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fulte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fulte(unwrap(lhs), unwrap(rhs)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_fugte(blk: pcb_BlockRef, lhs: pcb_ValueRef,
+    rhs: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_fugte(unwrap(lhs), unwrap(rhs)))
+}
+
+// int <-> float conversions
+#[no_mangle]
+pub unsafe extern fn pcb_build_ftoui(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ftoui(unwrap(value), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_ftosi(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_ftosi(unwrap(value), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_uitof(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_uitof(unwrap(value), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_sitof(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sitof(unwrap(value), unwrap(ty)))
+}
+
+// width conversions
+#[no_mangle]
+pub unsafe extern fn pcb_build_trunc(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_trunc(unwrap(value), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_zext(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_zext(unwrap(value), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_sext(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_sext(unwrap(value), unwrap(ty)))
+}
+#[no_mangle]
+pub unsafe extern fn pcb_build_bitcast(blk: pcb_BlockRef, value: pcb_ValueRef,
+    ty: pcb_TypeRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_bitcast(unwrap(value), unwrap(ty)))
+}
+
+// misc
+#[no_mangle]
+pub unsafe extern fn pcb_build_select(blk: pcb_BlockRef, cond: pcb_ValueRef,
+    then: pcb_ValueRef, else_: pcb_ValueRef) -> pcb_ValueRef {
+  wrap(unwrap(blk).build_select(unwrap(cond), unwrap(then), unwrap(else_)))
+}
+
 // terminators
 #[no_mangle]
 pub unsafe extern fn pcb_build_branch(blk: pcb_BlockRef,
-    to: pcb_BlockRef) {
-  unwrap(blk).build_branch(unwrap(to));
+    to: pcb_BlockRef, args: *const pcb_ValueRef, args_len: libc::size_t) {
+  let opaque = ptr_len_to_slice(args, args_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  unwrap(blk).build_branch(unwrap(to), &unwrapped);
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_cond_branch(blk: pcb_BlockRef,
+    cond: pcb_ValueRef, then: pcb_BlockRef, else_: pcb_BlockRef) {
+  unwrap(blk).build_cond_branch(unwrap(cond), unwrap(then), unwrap(else_));
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_switch(blk: pcb_BlockRef, value: pcb_ValueRef,
+    default: pcb_BlockRef, cases: *const pcb_SwitchCase,
+    cases_len: libc::size_t) {
+  let opaque = ptr_len_to_slice(cases, cases_len);
+  let mut unwrapped = vec![];
+  for case in opaque {
+    unwrapped.push((case.value, unwrap(case.block)));
+  }
+  unwrap(blk).build_switch(unwrap(value), unwrap(default), &unwrapped);
 }
 
 #[no_mangle]
@@ -204,6 +1090,44 @@ pub unsafe extern fn pcb_build_return(blk: pcb_BlockRef,
   unwrap(blk).build_return(unwrap(val))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_build_return_void(blk: pcb_BlockRef) {
+  unwrap(blk).build_return_void()
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_unreachable(blk: pcb_BlockRef) {
+  unwrap(blk).build_unreachable()
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_invoke(blk: pcb_BlockRef,
+    func: pcb_FunctionRef, args: *const pcb_ValueRef, args_len: libc::size_t,
+    normal: pcb_BlockRef, unwind: pcb_BlockRef) {
+  let opaque = ptr_len_to_slice(args, args_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  unwrap(blk).build_invoke(unwrap(func), &unwrapped, unwrap(normal),
+    unwrap(unwind))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_build_resume(blk: pcb_BlockRef,
+    exception: pcb_ValueRef) {
+  unwrap(blk).build_resume(unwrap(exception))
+}
+
+// == pcb_ValueRef ==
+
+#[no_mangle]
+pub unsafe extern fn pcb_set_value_name(val: pcb_ValueRef,
+    name: *const libc::c_char, name_len: libc::size_t) {
+  let name = ptr_len_to_str(name as *const u8, name_len);
+  unwrap(val).set_name(name)
+}
+
 // == pcb_TypeRef ==
 
 #[no_mangle]
@@ -212,6 +1136,50 @@ pub unsafe extern fn pcb_int_type(ctxt: *const pcb_Ctxt, size: u32)
   wrap(ty::Type::int(&(**ctxt).0, size))
 }
 
+#[no_mangle]
+pub unsafe extern fn pcb_float_type(ctxt: *const pcb_Ctxt, size: u32)
+    -> pcb_TypeRef {
+  wrap(ty::Type::float(&(**ctxt).0, size))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_bool_type(ctxt: *const pcb_Ctxt) -> pcb_TypeRef {
+  wrap(ty::Type::bool(&(**ctxt).0))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_void_type(ctxt: *const pcb_Ctxt) -> pcb_TypeRef {
+  wrap(ty::Type::void(&(**ctxt).0))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_pointer_type(ctxt: *const pcb_Ctxt) -> pcb_TypeRef {
+  wrap(ty::Type::pointer(&(**ctxt).0))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_function_pointer_type(ctxt: *const pcb_Ctxt,
+    ty: *const pcb_FunctionType) -> pcb_TypeRef {
+  wrap(ty::Type::function_pointer(&(**ctxt).0, &(**ty).0))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_aggregate_type(ctxt: *const pcb_Ctxt,
+    fields: *const pcb_TypeRef, fields_len: libc::size_t) -> pcb_TypeRef {
+  let opaque = ptr_len_to_slice(fields, fields_len);
+  let mut unwrapped = vec![];
+  for el in opaque {
+    unwrapped.push(unwrap(*el));
+  }
+  wrap(ty::Type::aggregate(&(**ctxt).0, &unwrapped))
+}
+
+#[no_mangle]
+pub unsafe extern fn pcb_array_type(ctxt: *const pcb_Ctxt,
+    element: pcb_TypeRef, len: u64) -> pcb_TypeRef {
+  wrap(ty::Type::array(&(**ctxt).0, unwrap(element), len))
+}
+
 // == pcb_llvm ==
 
 #[no_mangle]
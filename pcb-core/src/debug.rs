@@ -0,0 +1,34 @@
+use common::Context;
+use std::fmt::{self, Display, Formatter};
+
+pub type FileContext = Context<SourceFile>;
+
+// a source file of the frontend's language, for debug info
+pub struct SourceFile {
+  pub name: String,
+  pub directory: String,
+}
+
+// a position in a source file; lines and columns start at 1, and a column of
+// 0 means the whole line
+#[derive(Copy, Clone)]
+pub struct SourceLoc<'c> {
+  pub file: &'c SourceFile,
+  pub line: u32,
+  pub column: u32,
+}
+
+impl<'c> Display for SourceLoc<'c> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    write!(f, "{}:{}:{}", self.file.name, self.line, self.column)
+  }
+}
+
+// what a debugger shows for a function; the name is the one from the source,
+// rather than the symbol name
+pub struct FunctionDebugInfo<'c> {
+  pub name: String,
+  pub file: &'c SourceFile,
+  pub line: u32,
+  pub param_names: Box<[String]>,
+}
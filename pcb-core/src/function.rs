@@ -1,8 +1,11 @@
 use common::Context;
+use debug::{FunctionDebugInfo, SourceLoc};
+use global::Global;
 use ty;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 
 pub type FuncContext<'c> = Context<Function<'c>>;
 
@@ -11,15 +14,129 @@ pub struct Function<'c> {
   pub ty: ty::Function<'c>,
   pub blocks: BlockContext<'c>,
   pub values: ValueContext<'c>,
+  pub type_ctxt: &'c ty::TypeContext,
+  // declarations are defined externally, and have no blocks
+  pub declaration: bool,
+  pub linkage: Cell<Linkage>,
+  pub visibility: Cell<Visibility>,
+  // called by the unwinder to decide which landing pads to run; needed by
+  // functions with landing pads
+  pub personality: Cell<Option<&'c Function<'c>>>,
+  pub debug_info: RefCell<Option<FunctionDebugInfo<'c>>>,
+  // the names given to the function's values and blocks so far
+  pub names: RefCell<HashSet<String>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Linkage {
+  // visible to other object files
+  External,
+  // only visible within this object file
+  Internal,
+  // like internal, but not even present in the symbol table
+  Private,
+  // may be overridden by a definition in another object file
+  Weak,
+  // merged with definitions of the same name; the definitions must be
+  // equivalent
+  LinkOnceOdr,
+  // for zero-initialized, non-constant globals; merged with globals of the
+  // same name
+  Common,
+}
+
+impl Linkage {
+  // internal and private symbols can't be seen outside the object file
+  pub fn is_local(self) -> bool {
+    self == Linkage::Internal || self == Linkage::Private
+  }
+}
+
+impl Display for Linkage {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      Linkage::External => write!(f, "external"),
+      Linkage::Internal => write!(f, "internal"),
+      Linkage::Private => write!(f, "private"),
+      Linkage::Weak => write!(f, "weak"),
+      Linkage::LinkOnceOdr => write!(f, "linkonce_odr"),
+      Linkage::Common => write!(f, "common"),
+    }
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Visibility {
+  Default,
+  // not exported from the shared object it's linked into
+  Hidden,
+  // exported, but can't be preempted by another shared object
+  Protected,
+}
+
+impl Display for Visibility {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      Visibility::Default => write!(f, "default"),
+      Visibility::Hidden => write!(f, "hidden"),
+      Visibility::Protected => write!(f, "protected"),
+    }
+  }
+}
+
+// prints the linkage and visibility of a symbol, if they aren't the defaults,
+// each followed by a space
+pub fn fmt_symbol_attributes(f: &mut Formatter, linkage: Linkage,
+    visibility: Visibility) -> Result<(), fmt::Error> {
+  if linkage != Linkage::External {
+    try!(write!(f, "{} ", linkage));
+  }
+  if visibility != Visibility::Default {
+    try!(write!(f, "{} ", visibility));
+  }
+  Ok(())
 }
 
 impl<'c> Function<'c> {
-  pub fn add_block(&'c self) -> &'c Block<'c> {
+  pub fn add_block(&'c self, params: &[&'c ty::Type]) -> &'c Block<'c> {
+    self.push_block(params, false)
+  }
+
+  // a block which is only reached by unwinding out of an invoke; its first
+  // value is the exception being unwound
+  pub fn add_landing_pad(&'c self, cleanup: bool,
+      catches: Box<[Option<&'c Global<'c>>]>) -> &'c Block<'c> {
+    let blk = self.push_block(&[], true);
+    blk.add_value(ValueKind::LandingPad {
+      cleanup: cleanup,
+      catches: catches,
+    });
+    blk
+  }
+
+  fn push_block(&'c self, params: &[&'c ty::Type], landing_pad: bool)
+      -> &'c Block<'c> {
+    let mut param_values = vec![];
+    for &param_ty in params {
+      param_values.push(self.values.push(
+        Value {
+          number: self.values.len() as u32,
+          kind: ValueKind::BlockParameter(param_ty),
+          loc: None,
+          name: RefCell::new(None),
+          func: self,
+        }));
+    }
     self.blocks.push(
       Block {
         number: self.blocks.len() as u32,
-        terminator: Cell::new(Terminator::None),
+        params: param_values.into_boxed_slice(),
+        terminator: RefCell::new(Terminator::None),
         block_values: RefCell::new(vec![]),
+        landing_pad: landing_pad,
+        name: RefCell::new(None),
+        current_loc: Cell::new(None),
+        terminator_loc: Cell::new(None),
         func: self,
       })
   }
@@ -28,11 +145,46 @@ impl<'c> Function<'c> {
   pub fn ty(&self) -> &ty::Function<'c> {
     &self.ty
   }
+
+  // values and blocks share a namespace, as in LLVM. if `name` is already
+  // taken, or looks like the name of an unnamed value or block, a suffix is
+  // added to make it unique
+  pub fn unique_name(&self, name: &str) -> String {
+    fn is_numbered(name: &str) -> bool {
+      let digits = if name.starts_with("bb") { &name[2..] } else { name };
+      !digits.is_empty() && digits.chars().all(|c| c.is_digit(10))
+    }
+    let mut names = self.names.borrow_mut();
+    let mut ret = name.to_owned();
+    let mut suffix = 1;
+    while is_numbered(&ret) || names.contains(&ret) {
+      ret = format!("{}.{}", name, suffix);
+      suffix += 1;
+    }
+    names.insert(ret.clone());
+    ret
+  }
 }
 
 impl<'c> Display for Function<'c> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    try!(writeln!(f, "define {}{} {{", self.name, self.ty));
+    if self.declaration {
+      try!(write!(f, "declare "));
+    } else {
+      try!(write!(f, "define "));
+    }
+    try!(fmt_symbol_attributes(f, self.linkage.get(), self.visibility.get()));
+    if self.ty.call_conv != ty::CallConv::C {
+      try!(write!(f, "{} ", self.ty.call_conv));
+    }
+    if self.declaration {
+      return write!(f, "{}{}", self.name, self.ty);
+    }
+    try!(write!(f, "{}{}", self.name, self.ty));
+    if let Some(personality) = self.personality.get() {
+      try!(write!(f, " personality {}", personality.name));
+    }
+    try!(writeln!(f, " {{"));
     for blk in &self.blocks {
       try!(write!(f, "{:?}", blk));
     }
@@ -52,35 +204,236 @@ impl<'c> Hash for Function<'c> {
   }
 }
 
+// no signed wrap and no unsigned wrap; the result of an operation is
+// undefined if it wraps in a way that's been ruled out
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WrapFlags {
+  pub nsw: bool,
+  pub nuw: bool,
+}
+
+// each flag is followed by a space
+impl Display for WrapFlags {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    if self.nsw {
+      try!(write!(f, "nsw "));
+    }
+    if self.nuw {
+      try!(write!(f, "nuw "));
+    }
+    Ok(())
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AtomicOrdering {
+  Relaxed,
+  Acquire,
+  Release,
+  AcqRel,
+  SeqCst,
+}
+
+impl AtomicOrdering {
+  pub fn is_acquire(self) -> bool {
+    match self {
+      AtomicOrdering::Acquire | AtomicOrdering::AcqRel
+      | AtomicOrdering::SeqCst => true,
+      AtomicOrdering::Relaxed | AtomicOrdering::Release => false,
+    }
+  }
+
+  pub fn is_release(self) -> bool {
+    match self {
+      AtomicOrdering::Release | AtomicOrdering::AcqRel
+      | AtomicOrdering::SeqCst => true,
+      AtomicOrdering::Relaxed | AtomicOrdering::Acquire => false,
+    }
+  }
+}
+
+impl Display for AtomicOrdering {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      AtomicOrdering::Relaxed => write!(f, "relaxed"),
+      AtomicOrdering::Acquire => write!(f, "acquire"),
+      AtomicOrdering::Release => write!(f, "release"),
+      AtomicOrdering::AcqRel => write!(f, "acq_rel"),
+      AtomicOrdering::SeqCst => write!(f, "seq_cst"),
+    }
+  }
+}
+
+// the operations of atomic read-modify-write
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AtomicRmwOp {
+  // exchange
+  Xchg,
+  Add,
+  Sub,
+  And,
+  Or,
+  Xor,
+  SMax,
+  SMin,
+  UMax,
+  UMin,
+}
+
+impl Display for AtomicRmwOp {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match *self {
+      AtomicRmwOp::Xchg => write!(f, "xchg"),
+      AtomicRmwOp::Add => write!(f, "add"),
+      AtomicRmwOp::Sub => write!(f, "sub"),
+      AtomicRmwOp::And => write!(f, "and"),
+      AtomicRmwOp::Or => write!(f, "or"),
+      AtomicRmwOp::Xor => write!(f, "xor"),
+      AtomicRmwOp::SMax => write!(f, "smax"),
+      AtomicRmwOp::SMin => write!(f, "smin"),
+      AtomicRmwOp::UMax => write!(f, "umax"),
+      AtomicRmwOp::UMin => write!(f, "umin"),
+    }
+  }
+}
+
 pub type BlockContext<'c> = Context<Block<'c>>;
 
-#[derive(Copy, Clone)]
 pub enum Terminator<'c> {
-  Branch(&'c Block<'c>),
+  // the values are passed to the parameters of the block
+  Branch(&'c Block<'c>, Box<[&'c Value<'c>]>),
+  CondBranch {
+    cond: &'c Value<'c>,
+    then: &'c Block<'c>,
+    else_: &'c Block<'c>,
+  },
+  Switch {
+    value: &'c Value<'c>,
+    default: &'c Block<'c>,
+    cases: Box<[(u64, &'c Block<'c>)]>,
+  },
+  // calls the function; if it returns, the result (if it isn't void) is passed
+  // to the parameter of `normal`. if it unwinds, control goes to the landing
+  // pad `unwind`
+  Invoke {
+    function: &'c Function<'c>,
+    parameters: Box<[&'c Value<'c>]>,
+    normal: &'c Block<'c>,
+    unwind: &'c Block<'c>,
+  },
+  // continues unwinding an exception caught by a landing pad
+  Resume(&'c Value<'c>),
   // final return in a function
   Return(&'c Value<'c>),
+  ReturnVoid,
+  // for paths that can never be reached, like after a diverging call
+  Unreachable,
   None,
 }
 
 impl<'c> Terminator<'c> {
-  pub fn is_none(self) -> bool {
-    if let Terminator::None = self {
+  pub fn is_none(&self) -> bool {
+    if let Terminator::None = *self {
       true
     } else {
       false
     }
   }
+
+  pub fn successors(&self) -> Vec<&'c Block<'c>> {
+    match *self {
+      Terminator::Branch(b, _) => vec![b],
+      Terminator::CondBranch {
+        then,
+        else_,
+        ..
+      } => vec![then, else_],
+      Terminator::Switch {
+        default,
+        ref cases,
+        ..
+      } => {
+        let mut ret = vec![default];
+        for &(_, blk) in cases.iter() {
+          ret.push(blk);
+        }
+        ret
+      }
+      Terminator::Invoke {
+        normal,
+        unwind,
+        ..
+      } => vec![normal, unwind],
+      Terminator::Resume(_)
+      | Terminator::Return(_) | Terminator::ReturnVoid
+      | Terminator::Unreachable | Terminator::None => vec![],
+    }
+  }
 }
 
 impl<'c> Display for Terminator<'c> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     match *self {
-      Terminator::Branch(b) => {
-        write!(f, "branch {}", b)
+      Terminator::Branch(b, ref args) => {
+        try!(write!(f, "branch {}", b));
+        if !args.is_empty() {
+          try!(write!(f, "("));
+          for arg in &args[..args.len() - 1] {
+            try!(write!(f, "{}, ", arg));
+          }
+          try!(write!(f, "{})", args[args.len() - 1]));
+        }
+        Ok(())
       },
+      Terminator::CondBranch {
+        cond,
+        then,
+        else_,
+      } => {
+        write!(f, "cond_branch {} {} {}", cond, then, else_)
+      },
+      Terminator::Switch {
+        value,
+        default,
+        ref cases,
+      } => {
+        try!(write!(f, "switch {} {} [", value, default));
+        if !cases.is_empty() {
+          for &(n, blk) in &cases[..cases.len() - 1] {
+            try!(write!(f, "{}: {}, ", n, blk));
+          }
+          let (n, blk) = cases[cases.len() - 1];
+          try!(write!(f, "{}: {}", n, blk));
+        }
+        write!(f, "]")
+      },
+      Terminator::Invoke {
+        function,
+        ref parameters,
+        normal,
+        unwind,
+      } => {
+        try!(write!(f, "invoke {}(", function.name));
+        if !parameters.is_empty() {
+          for param in &parameters[..parameters.len() - 1] {
+            try!(write!(f, "{}, ", param));
+          }
+          try!(write!(f, "{}", parameters[parameters.len() - 1]));
+        }
+        write!(f, ") {} unwind {}", normal, unwind)
+      }
+      Terminator::Resume(exception) => {
+        write!(f, "resume {}", exception)
+      }
       Terminator::Return(r) => {
         write!(f, "return {}", r)
       }
+      Terminator::ReturnVoid => {
+        write!(f, "return")
+      }
+      Terminator::Unreachable => {
+        write!(f, "unreachable")
+      }
       Terminator::None => { Ok(()) }
     }
   }
@@ -91,8 +444,16 @@ impl<'c> Display for Terminator<'c> {
 // .build_return, .build_branch, etc.
 pub struct Block<'c> {
   pub number: u32,
-  pub terminator: Cell<Terminator<'c>>,
+  pub params: Box<[&'c Value<'c>]>,
+  pub terminator: RefCell<Terminator<'c>>,
   pub block_values: RefCell<Vec<&'c Value<'c>>>,
+  // landing pads may only be branched to by an invoke's unwind edge
+  pub landing_pad: bool,
+  // unique within the function; unnamed blocks are printed as bb{number}
+  pub name: RefCell<Option<String>>,
+  // given to values and the terminator as they're built
+  pub current_loc: Cell<Option<SourceLoc<'c>>>,
+  pub terminator_loc: Cell<Option<SourceLoc<'c>>>,
   pub func: &'c Function<'c>,
 }
 
@@ -102,27 +463,63 @@ impl<'c> Block<'c> {
       Value {
         number: self.func.values.len() as u32,
         kind: kind,
+        loc: self.current_loc.get(),
+        name: RefCell::new(None),
         func: &self.func,
       });
     self.block_values.borrow_mut().push(ret);
     ret
   }
+
+  pub fn set_terminator(&self, terminator: Terminator<'c>) {
+    *self.terminator.borrow_mut() = terminator;
+    self.terminator_loc.set(self.current_loc.get());
+  }
 }
 
 impl<'c> Debug for Block<'c> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    try!(writeln!(f, "{}:", self));
+    try!(write!(f, "{}", self));
+    if !self.params.is_empty() {
+      try!(write!(f, "("));
+      for param in &self.params[..self.params.len() - 1] {
+        try!(write!(f, "{}: {}, ", param, param.ty()));
+      }
+      let last = self.params[self.params.len() - 1];
+      try!(write!(f, "{}: {})", last, last.ty()));
+    }
+    if self.landing_pad {
+      try!(write!(f, " landing_pad"));
+    }
+    try!(writeln!(f, ":"));
     for value in &*self.block_values.borrow() {
-      try!(writeln!(f, "  {}: {} = {:?}", value, value.ty(), value));
+      if *value.ty() == ty::Type::Void {
+        try!(write!(f, "  {:?}", value));
+      } else {
+        try!(write!(f, "  {}: {} = {:?}", value, value.ty(), value));
+      }
+      try!(fmt_loc(f, value.loc));
     }
-    try!(writeln!(f, "  {}", self.terminator.get()));
-    Ok(())
+    try!(write!(f, "  {}", *self.terminator.borrow()));
+    fmt_loc(f, self.terminator_loc.get())
+  }
+}
+
+// ends the line, after the source location if there is one
+fn fmt_loc(f: &mut Formatter, loc: Option<SourceLoc>)
+    -> Result<(), fmt::Error> {
+  match loc {
+    Some(loc) => writeln!(f, " @ {}", loc),
+    None => writeln!(f, ""),
   }
 }
 
 impl<'c> Display for Block<'c> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    write!(f, "bb{}", self.number)
+    match *self.name.borrow() {
+      Some(ref name) => write!(f, "{}", name),
+      None => write!(f, "bb{}", self.number),
+    }
   }
 }
 
@@ -131,43 +528,194 @@ pub type ValueContext<'c> = Context<Value<'c>>;
 pub struct Value<'c> {
   pub number: u32,
   pub kind: ValueKind<'c>,
+  // where in the frontend's source the value comes from
+  pub loc: Option<SourceLoc<'c>>,
+  // unique within the function; unnamed values are printed as %{number}
+  pub name: RefCell<Option<String>>,
   pub func: &'c Function<'c>,
 }
 impl<'c> Value<'c> {
+  pub fn is_const(&self) -> bool {
+    match self.kind {
+      ValueKind::ConstInt { .. } | ValueKind::ConstFloat { .. }
+      | ValueKind::ConstBool(_)
+      | ValueKind::ConstAggregate { .. }
+      | ValueKind::ConstArray { .. } => true,
+      _ => false,
+    }
+  }
+
+  pub fn is_tail_call(&self) -> bool {
+    match self.kind {
+      ValueKind::Call { tail, .. } | ValueKind::CallIndirect { tail, .. } => {
+        tail
+      }
+      _ => false,
+    }
+  }
+
   pub fn ty(&self) -> &'c ty::Type {
     match self.kind {
       ValueKind::ConstInt {
         ty,
         ..
       } => ty,
+      ValueKind::ConstFloat {
+        ty,
+        ..
+      } => ty,
+      ValueKind::ConstBool(_) => self.func.type_ctxt.get(ty::Type::Bool),
+      ValueKind::ConstAggregate {
+        ty,
+        ..
+      } => ty,
+      ValueKind::ConstArray {
+        ty,
+        ..
+      } => ty,
       ValueKind::Call {
         function,
         ..
       } => function.ty.output,
-      ValueKind::Mul(lhs, _) => lhs.ty(),
-      ValueKind::UDiv(lhs, _) => lhs.ty(),
-      ValueKind::SDiv(lhs, _) => lhs.ty(),
+      ValueKind::CallIndirect {
+        callee,
+        ..
+      } => callee.ty().fn_ptr_output(),
+      ValueKind::InlineAsm {
+        ty,
+        ..
+      } => ty,
+      ValueKind::FunctionAddress(function) =>
+        self.func.type_ctxt.get(function.ty.pointer_type()),
+      ValueKind::GlobalAddress(_) => self.func.type_ctxt.get(ty::Type::Pointer),
+      ValueKind::LandingPad { .. } =>
+        self.func.type_ctxt.get(ty::Type::exception()),
+      ValueKind::ExtractField {
+        value,
+        index,
+      } => &value.ty().aggregate_fields()[index as usize],
+      ValueKind::InsertField {
+        value,
+        ..
+      } => value.ty(),
+
+      ValueKind::Alloca(_) => self.func.type_ctxt.get(ty::Type::Pointer),
+      ValueKind::Load {
+        ty,
+        ..
+      } => ty,
+      ValueKind::Store { .. } => self.func.type_ctxt.get(ty::Type::Void),
+      ValueKind::FieldAddress { .. } | ValueKind::ElementAddress { .. } =>
+        self.func.type_ctxt.get(ty::Type::Pointer),
+
+      ValueKind::AtomicLoad {
+        ty,
+        ..
+      } => ty,
+      ValueKind::AtomicStore { .. } | ValueKind::Fence(_) =>
+        self.func.type_ctxt.get(ty::Type::Void),
+      ValueKind::AtomicRmw {
+        value,
+        ..
+      } => value.ty(),
+      ValueKind::CmpXchg {
+        expected,
+        ..
+      } => self.func.type_ctxt.get(ty::Type::Aggregate(
+        vec![expected.ty().clone(), ty::Type::Bool])),
+
+      ValueKind::VaStart => self.func.type_ctxt.get(ty::Type::Pointer),
+      ValueKind::VaArg {
+        ty,
+        ..
+      } => ty,
+      ValueKind::VaEnd(_) => self.func.type_ctxt.get(ty::Type::Void),
+
+      ValueKind::Neg(inner) => inner.ty(),
+      ValueKind::Not(inner) => inner.ty(),
+
+      ValueKind::Mul(lhs, _, _) => lhs.ty(),
+      ValueKind::UDiv(lhs, _, _) => lhs.ty(),
+      ValueKind::SDiv(lhs, _, _) => lhs.ty(),
       ValueKind::URem(lhs, _) => lhs.ty(),
       ValueKind::SRem(lhs, _) => lhs.ty(),
 
-      ValueKind::Add(lhs, _) => lhs.ty(),
-      ValueKind::Sub(lhs, _) => lhs.ty(),
+      ValueKind::Add(lhs, _, _) => lhs.ty(),
+      ValueKind::Sub(lhs, _, _) => lhs.ty(),
 
-      ValueKind::Shl(lhs, _) => lhs.ty(),
-      ValueKind::ZShr(lhs, _) => lhs.ty(),
-      ValueKind::SShr(lhs, _) => lhs.ty(),
+      ValueKind::Shl(lhs, _, _) => lhs.ty(),
+      ValueKind::ZShr(lhs, _, _) => lhs.ty(),
+      ValueKind::SShr(lhs, _, _) => lhs.ty(),
 
       ValueKind::And(lhs, _) => lhs.ty(),
       ValueKind::Xor(lhs, _) => lhs.ty(),
       ValueKind::Or(lhs, _) => lhs.ty(),
 
-      ValueKind::Eq(_, _) => unimplemented!(),
-      ValueKind::Neq(_, _) => unimplemented!(),
-      ValueKind::Lt(_, _) => unimplemented!(),
-      ValueKind::Gt(_, _) => unimplemented!(),
-      ValueKind::Lte(_, _) => unimplemented!(),
-      ValueKind::Gte(_, _) => unimplemented!(),
+      ValueKind::SAddOverflow(lhs, _)
+      | ValueKind::UAddOverflow(lhs, _)
+      | ValueKind::SSubOverflow(lhs, _)
+      | ValueKind::USubOverflow(lhs, _)
+      | ValueKind::SMulOverflow(lhs, _)
+      | ValueKind::UMulOverflow(lhs, _) =>
+        self.func.type_ctxt.get(ty::Type::Aggregate(
+          vec![lhs.ty().clone(), ty::Type::Bool])),
+      ValueKind::SAddSat(lhs, _) => lhs.ty(),
+      ValueKind::UAddSat(lhs, _) => lhs.ty(),
+      ValueKind::SSubSat(lhs, _) => lhs.ty(),
+      ValueKind::USubSat(lhs, _) => lhs.ty(),
+
+      ValueKind::Ctpop(inner)
+      | ValueKind::Ctlz(inner)
+      | ValueKind::Cttz(inner)
+      | ValueKind::Bswap(inner)
+      | ValueKind::BitReverse(inner) => inner.ty(),
+      ValueKind::Fshl {
+        hi,
+        ..
+      } => hi.ty(),
+      ValueKind::Fshr {
+        hi,
+        ..
+      } => hi.ty(),
+
+      ValueKind::Eq(_, _) | ValueKind::Neq(_, _)
+      | ValueKind::ULt(_, _) | ValueKind::SLt(_, _)
+      | ValueKind::UGt(_, _) | ValueKind::SGt(_, _)
+      | ValueKind::ULte(_, _) | ValueKind::SLte(_, _)
+      | ValueKind::UGte(_, _) | ValueKind::SGte(_, _) =>
+        self.func.type_ctxt.get(ty::Type::Bool),
+
+      ValueKind::FAdd(lhs, _) => lhs.ty(),
+      ValueKind::FSub(lhs, _) => lhs.ty(),
+      ValueKind::FMul(lhs, _) => lhs.ty(),
+      ValueKind::FDiv(lhs, _) => lhs.ty(),
+      ValueKind::FRem(lhs, _) => lhs.ty(),
+
+      ValueKind::FOEq(_, _) | ValueKind::FONeq(_, _)
+      | ValueKind::FOLt(_, _) | ValueKind::FOGt(_, _)
+      | ValueKind::FOLte(_, _) | ValueKind::FOGte(_, _)
+      | ValueKind::FUEq(_, _) | ValueKind::FUNeq(_, _)
+      | ValueKind::FULt(_, _) | ValueKind::FUGt(_, _)
+      | ValueKind::FULte(_, _) | ValueKind::FUGte(_, _) =>
+        self.func.type_ctxt.get(ty::Type::Bool),
+
+      ValueKind::FToUI { ty, .. }
+      | ValueKind::FToSI { ty, .. }
+      | ValueKind::UIToF { ty, .. }
+      | ValueKind::SIToF { ty, .. } => ty,
+
+      ValueKind::Trunc { ty, .. }
+      | ValueKind::ZExt { ty, .. }
+      | ValueKind::SExt { ty, .. }
+      | ValueKind::Bitcast { ty, .. } => ty,
+
+      ValueKind::Select {
+        then,
+        ..
+      } => then.ty(),
+
       ValueKind::Parameter(ty) => ty,
+      ValueKind::BlockParameter(ty) => ty,
     }
   }
 
@@ -178,38 +726,283 @@ pub enum ValueKind<'c> {
     ty: &'c ty::Type,
     value: u64,
   },
+  ConstFloat {
+    ty: &'c ty::Type,
+    value: f64,
+  },
+  ConstBool(bool),
+  // the fields must be constants
+  ConstAggregate {
+    ty: &'c ty::Type,
+    fields: Box<[&'c Value<'c>]>,
+  },
+  // the elements must be constants
+  ConstArray {
+    ty: &'c ty::Type,
+    elements: Box<[&'c Value<'c>]>,
+  },
+  // tail calls reuse the caller's stack frame, so the callee must not access
+  // the caller's allocas. they're always the last value of their block, and
+  // the block returns their result
   Call {
     function: &'c Function<'c>,
-    parameters: Box<[&'c Value<'c>]>
+    parameters: Box<[&'c Value<'c>]>,
+    tail: bool,
+  },
+  // calls through a function pointer
+  CallIndirect {
+    callee: &'c Value<'c>,
+    parameters: Box<[&'c Value<'c>]>,
+    tail: bool,
+  },
+  // `constraints` is in LLVM's constraint syntax; multiple outputs are
+  // returned as an aggregate of type `ty`
+  InlineAsm {
+    template: String,
+    constraints: String,
+    side_effects: bool,
+    align_stack: bool,
+    operands: Box<[&'c Value<'c>]>,
+    ty: &'c ty::Type,
+  },
+  FunctionAddress(&'c Function<'c>),
+  GlobalAddress(&'c Global<'c>),
+
+  // -- exceptions --
+  // the first value of a landing pad; the exception being unwound, of type
+  // (ptr, i32). the catches are the type infos of the exceptions that are
+  // caught, with None catching every exception. cleanup landing pads are run
+  // for every exception
+  LandingPad {
+    cleanup: bool,
+    catches: Box<[Option<&'c Global<'c>>]>,
+  },
+
+  // -- aggregates --
+  ExtractField {
+    value: &'c Value<'c>,
+    index: u32,
+  },
+  InsertField {
+    value: &'c Value<'c>,
+    index: u32,
+    field: &'c Value<'c>,
+  },
+
+  // -- memory --
+  Alloca(&'c ty::Type),
+  Load {
+    ptr: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  Store {
+    ptr: &'c Value<'c>,
+    value: &'c Value<'c>,
+  },
+  // the address of a field of the aggregate of type `ty` pointed to by `ptr`
+  FieldAddress {
+    ptr: &'c Value<'c>,
+    ty: &'c ty::Type,
+    index: u32,
+  },
+  // the address of an element of the array of type `ty` pointed to by `ptr`
+  ElementAddress {
+    ptr: &'c Value<'c>,
+    ty: &'c ty::Type,
+    index: &'c Value<'c>,
+  },
+
+  // -- atomics --
+  AtomicLoad {
+    ptr: &'c Value<'c>,
+    ty: &'c ty::Type,
+    ordering: AtomicOrdering,
   },
+  AtomicStore {
+    ptr: &'c Value<'c>,
+    value: &'c Value<'c>,
+    ordering: AtomicOrdering,
+  },
+  // returns the value that was previously stored
+  AtomicRmw {
+    op: AtomicRmwOp,
+    ptr: &'c Value<'c>,
+    value: &'c Value<'c>,
+    ordering: AtomicOrdering,
+  },
+  // stores `new` if the value stored is `expected`; returns an aggregate of
+  // the value that was previously stored, and whether the store happened
+  CmpXchg {
+    ptr: &'c Value<'c>,
+    expected: &'c Value<'c>,
+    new: &'c Value<'c>,
+    success: AtomicOrdering,
+    failure: AtomicOrdering,
+  },
+  Fence(AtomicOrdering),
+
+  // -- varargs; only allowed in variadic functions --
+  // returns a pointer to a fresh va_list, started at the variadic arguments
+  VaStart,
+  // the next variadic argument, read as `ty`, from the va_list at `list`
+  VaArg {
+    list: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  VaEnd(&'c Value<'c>),
+
+  // -- unary ops --
+  // two's complement negation
+  Neg(&'c Value<'c>),
+  // bitwise not; logical not for bools
+  Not(&'c Value<'c>),
 
   // -- binops --
-  Mul(&'c Value<'c>, &'c Value<'c>),
-  UDiv(&'c Value<'c>, &'c Value<'c>),
-  SDiv(&'c Value<'c>, &'c Value<'c>),
+  // the flags and exactness make the result undefined if they don't hold
+  Mul(&'c Value<'c>, &'c Value<'c>, WrapFlags),
+  // exact if the division is known to have no remainder
+  UDiv(&'c Value<'c>, &'c Value<'c>, bool),
+  SDiv(&'c Value<'c>, &'c Value<'c>, bool),
   URem(&'c Value<'c>, &'c Value<'c>),
   SRem(&'c Value<'c>, &'c Value<'c>),
 
-  Add(&'c Value<'c>, &'c Value<'c>),
-  Sub(&'c Value<'c>, &'c Value<'c>),
+  Add(&'c Value<'c>, &'c Value<'c>, WrapFlags),
+  Sub(&'c Value<'c>, &'c Value<'c>, WrapFlags),
 
-  Shl(&'c Value<'c>, &'c Value<'c>),
-  ZShr(&'c Value<'c>, &'c Value<'c>), // zero-extend
-  SShr(&'c Value<'c>, &'c Value<'c>), // sign-extend
+  Shl(&'c Value<'c>, &'c Value<'c>, WrapFlags),
+  // exact if no set bits are shifted out
+  ZShr(&'c Value<'c>, &'c Value<'c>, bool), // zero-extend
+  SShr(&'c Value<'c>, &'c Value<'c>, bool), // sign-extend
 
   And(&'c Value<'c>, &'c Value<'c>),
   Xor(&'c Value<'c>, &'c Value<'c>),
   Or(&'c Value<'c>, &'c Value<'c>),
 
+  // -- checked arithmetic --
+  // these return an aggregate of the result, wrapped on overflow, and a bool
+  // which is true if the operation overflowed
+  SAddOverflow(&'c Value<'c>, &'c Value<'c>),
+  UAddOverflow(&'c Value<'c>, &'c Value<'c>),
+  SSubOverflow(&'c Value<'c>, &'c Value<'c>),
+  USubOverflow(&'c Value<'c>, &'c Value<'c>),
+  SMulOverflow(&'c Value<'c>, &'c Value<'c>),
+  UMulOverflow(&'c Value<'c>, &'c Value<'c>),
+  // these clamp the result to the range of the type on overflow
+  SAddSat(&'c Value<'c>, &'c Value<'c>),
+  UAddSat(&'c Value<'c>, &'c Value<'c>),
+  SSubSat(&'c Value<'c>, &'c Value<'c>),
+  USubSat(&'c Value<'c>, &'c Value<'c>),
+
+  // -- bit manipulation --
+  // the number of set bits
+  Ctpop(&'c Value<'c>),
+  // the number of leading and trailing zero bits; the width of the type if
+  // the value is zero
+  Ctlz(&'c Value<'c>),
+  Cttz(&'c Value<'c>),
+  // the type must be a multiple of 16 bits wide
+  Bswap(&'c Value<'c>),
+  BitReverse(&'c Value<'c>),
+  // funnel shifts; `hi` and `lo` are concatenated, shifted by `amount`
+  // modulo the width, and the high (for fshl) or low (for fshr) half is
+  // returned. with `hi` and `lo` the same, these are rotates
+  Fshl {
+    hi: &'c Value<'c>,
+    lo: &'c Value<'c>,
+    amount: &'c Value<'c>,
+  },
+  Fshr {
+    hi: &'c Value<'c>,
+    lo: &'c Value<'c>,
+    amount: &'c Value<'c>,
+  },
+
+  // -- comparisons; these return bool --
   Eq(&'c Value<'c>, &'c Value<'c>),
   Neq(&'c Value<'c>, &'c Value<'c>),
-  Lt(&'c Value<'c>, &'c Value<'c>),
-  Gt(&'c Value<'c>, &'c Value<'c>),
-  Lte(&'c Value<'c>, &'c Value<'c>),
-  Gte(&'c Value<'c>, &'c Value<'c>),
+  ULt(&'c Value<'c>, &'c Value<'c>),
+  SLt(&'c Value<'c>, &'c Value<'c>),
+  UGt(&'c Value<'c>, &'c Value<'c>),
+  SGt(&'c Value<'c>, &'c Value<'c>),
+  ULte(&'c Value<'c>, &'c Value<'c>),
+  SLte(&'c Value<'c>, &'c Value<'c>),
+  UGte(&'c Value<'c>, &'c Value<'c>),
+  SGte(&'c Value<'c>, &'c Value<'c>),
+
+  // -- float binops --
+  FAdd(&'c Value<'c>, &'c Value<'c>),
+  FSub(&'c Value<'c>, &'c Value<'c>),
+  FMul(&'c Value<'c>, &'c Value<'c>),
+  FDiv(&'c Value<'c>, &'c Value<'c>),
+  FRem(&'c Value<'c>, &'c Value<'c>),
+
+  // -- float comparisons; these return bool --
+  // ordered; false if either operand is NaN
+  FOEq(&'c Value<'c>, &'c Value<'c>),
+  FONeq(&'c Value<'c>, &'c Value<'c>),
+  FOLt(&'c Value<'c>, &'c Value<'c>),
+  FOGt(&'c Value<'c>, &'c Value<'c>),
+  FOLte(&'c Value<'c>, &'c Value<'c>),
+  FOGte(&'c Value<'c>, &'c Value<'c>),
+  // unordered; true if either operand is NaN
+  FUEq(&'c Value<'c>, &'c Value<'c>),
+  FUNeq(&'c Value<'c>, &'c Value<'c>),
+  FULt(&'c Value<'c>, &'c Value<'c>),
+  FUGt(&'c Value<'c>, &'c Value<'c>),
+  FULte(&'c Value<'c>, &'c Value<'c>),
+  FUGte(&'c Value<'c>, &'c Value<'c>),
+
+  // -- int <-> float conversions; `ty` is the type converted to --
+  FToUI {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  FToSI {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  UIToF {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  SIToF {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+
+  // -- width conversions; `ty` is the type converted to --
+  Trunc {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  // zero-extend
+  ZExt {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  // sign-extend
+  SExt {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+  // reinterprets the bits of the value as a type of the same size
+  Bitcast {
+    value: &'c Value<'c>,
+    ty: &'c ty::Type,
+  },
+
+  // `then` if `cond` is true, otherwise `else_`
+  Select {
+    cond: &'c Value<'c>,
+    then: &'c Value<'c>,
+    else_: &'c Value<'c>,
+  },
 
   // parameter (this *may not* be built; it's simply a placeholder)
   Parameter(&'c ty::Type),
+  // block parameter; like Parameter, it's a placeholder, filled in by the
+  // arguments of the branches to the block
+  BlockParameter(&'c ty::Type),
 }
 
 impl<'c> Debug for Value<'c> {
@@ -221,45 +1014,336 @@ impl<'c> Debug for Value<'c> {
       } => {
         try!(write!(f, "{}", value));
       }
+      ValueKind::ConstFloat {
+        value,
+        ..
+      } => try!(write!(f, "{:?}", value)),
+      ValueKind::ConstBool(value) => try!(write!(f, "{}", value)),
+      ValueKind::ConstAggregate {
+        ref fields,
+        ..
+      } => {
+        try!(write!(f, "("));
+        if !fields.is_empty() {
+          for field in &fields[..fields.len() - 1] {
+            try!(write!(f, "{}, ", field));
+          }
+          try!(write!(f, "{}", fields[fields.len() - 1]));
+        }
+        try!(write!(f, ")"));
+      }
+      ValueKind::ConstArray {
+        ref elements,
+        ..
+      } => {
+        try!(write!(f, "["));
+        if !elements.is_empty() {
+          for element in &elements[..elements.len() - 1] {
+            try!(write!(f, "{}, ", element));
+          }
+          try!(write!(f, "{}", elements[elements.len() - 1]));
+        }
+        try!(write!(f, "]"));
+      }
       ValueKind::Call {
         function,
-        ref parameters
+        ref parameters,
+        tail,
       } => {
+        if tail {
+          try!(write!(f, "tail "));
+        }
         try!(write!(f, "call {}(", function.name));
         if !parameters.is_empty() {
-          for i in 0..parameters.len() - 1 {
-            try!(write!(f, "%{}, ", i));
+          for param in &parameters[..parameters.len() - 1] {
+            try!(write!(f, "{}, ", param));
           }
-          try!(write!(f, "%{}", parameters.len() - 1));
+          try!(write!(f, "{}", parameters[parameters.len() - 1]));
         }
         try!(write!(f, ")"));
       }
-      ValueKind::Mul(lhs, rhs) => try!(write!(f, "mul {} {}", lhs, rhs)),
-      ValueKind::UDiv(lhs, rhs) => try!(write!(f, "udiv {} {}", lhs, rhs)),
-      ValueKind::SDiv(lhs, rhs) => try!(write!(f, "sdiv {} {}", lhs, rhs)),
+      ValueKind::CallIndirect {
+        callee,
+        ref parameters,
+        tail,
+      } => {
+        if tail {
+          try!(write!(f, "tail "));
+        }
+        try!(write!(f, "call_indirect {}(", callee));
+        if !parameters.is_empty() {
+          for param in &parameters[..parameters.len() - 1] {
+            try!(write!(f, "{}, ", param));
+          }
+          try!(write!(f, "{}", parameters[parameters.len() - 1]));
+        }
+        try!(write!(f, ")"));
+      }
+      ValueKind::InlineAsm {
+        ref template,
+        ref constraints,
+        side_effects,
+        align_stack,
+        ref operands,
+        ty,
+      } => {
+        try!(write!(f, "inline_asm "));
+        if side_effects {
+          try!(write!(f, "sideeffect "));
+        }
+        if align_stack {
+          try!(write!(f, "alignstack "));
+        }
+        try!(write!(f, "{:?}, {:?} (", template, constraints));
+        if !operands.is_empty() {
+          for operand in &operands[..operands.len() - 1] {
+            try!(write!(f, "{}, ", operand));
+          }
+          try!(write!(f, "{}", operands[operands.len() - 1]));
+        }
+        try!(write!(f, ") -> {}", ty));
+      }
+      ValueKind::FunctionAddress(function) =>
+        try!(write!(f, "function_address {}", function.name)),
+      ValueKind::GlobalAddress(global) =>
+        try!(write!(f, "global_address {}", global.name)),
+      ValueKind::LandingPad {
+        cleanup,
+        ref catches,
+      } => {
+        try!(write!(f, "landing_pad"));
+        if cleanup {
+          try!(write!(f, " cleanup"));
+        }
+        for catch in catches.iter() {
+          match *catch {
+            Some(global) => try!(write!(f, " catch {}", global.name)),
+            None => try!(write!(f, " catch_all")),
+          }
+        }
+      }
+      ValueKind::ExtractField {
+        value,
+        index,
+      } => try!(write!(f, "extract_field {} {}", value, index)),
+      ValueKind::InsertField {
+        value,
+        index,
+        field,
+      } => try!(write!(f, "insert_field {} {} {}", value, index, field)),
+
+      ValueKind::Alloca(ty) => try!(write!(f, "alloca {}", ty)),
+      ValueKind::Load {
+        ptr,
+        ty,
+      } => try!(write!(f, "load {} {}", ty, ptr)),
+      ValueKind::Store {
+        ptr,
+        value,
+      } => try!(write!(f, "store {} {}", ptr, value)),
+      ValueKind::FieldAddress {
+        ptr,
+        ty,
+        index,
+      } => try!(write!(f, "field_address {} {} {}", ty, ptr, index)),
+      ValueKind::ElementAddress {
+        ptr,
+        ty,
+        index,
+      } => try!(write!(f, "element_address {} {} {}", ty, ptr, index)),
+
+      ValueKind::AtomicLoad {
+        ptr,
+        ty,
+        ordering,
+      } => try!(write!(f, "atomic_load {} {} {}", ordering, ty, ptr)),
+      ValueKind::AtomicStore {
+        ptr,
+        value,
+        ordering,
+      } => try!(write!(f, "atomic_store {} {} {}", ordering, ptr, value)),
+      ValueKind::AtomicRmw {
+        op,
+        ptr,
+        value,
+        ordering,
+      } => try!(write!(f, "atomic_rmw {} {} {} {}", op, ordering, ptr, value)),
+      ValueKind::CmpXchg {
+        ptr,
+        expected,
+        new,
+        success,
+        failure,
+      } => try!(write!(f, "cmpxchg {} {} {} {} {}", success, failure, ptr,
+        expected, new)),
+      ValueKind::Fence(ordering) => try!(write!(f, "fence {}", ordering)),
+
+      ValueKind::VaStart => try!(write!(f, "va_start")),
+      ValueKind::VaArg {
+        list,
+        ty,
+      } => try!(write!(f, "va_arg {} {}", ty, list)),
+      ValueKind::VaEnd(list) => try!(write!(f, "va_end {}", list)),
+
+      ValueKind::Neg(inner) => try!(write!(f, "neg {}", inner)),
+      ValueKind::Not(inner) => try!(write!(f, "not {}", inner)),
+
+      ValueKind::Mul(lhs, rhs, flags) =>
+        try!(write!(f, "mul {}{} {}", flags, lhs, rhs)),
+      ValueKind::UDiv(lhs, rhs, exact) => {
+        try!(write!(f, "udiv "));
+        if exact {
+          try!(write!(f, "exact "));
+        }
+        try!(write!(f, "{} {}", lhs, rhs));
+      }
+      ValueKind::SDiv(lhs, rhs, exact) => {
+        try!(write!(f, "sdiv "));
+        if exact {
+          try!(write!(f, "exact "));
+        }
+        try!(write!(f, "{} {}", lhs, rhs));
+      }
       ValueKind::URem(lhs, rhs) => try!(write!(f, "urem {} {}", lhs, rhs)),
       ValueKind::SRem(lhs, rhs) => try!(write!(f, "srem {} {}", lhs, rhs)),
 
-      ValueKind::Add(lhs, rhs) => try!(write!(f, "add {} {}", lhs, rhs)),
-      ValueKind::Sub(lhs, rhs) => try!(write!(f, "sub {} {}", lhs, rhs)),
+      ValueKind::Add(lhs, rhs, flags) =>
+        try!(write!(f, "add {}{} {}", flags, lhs, rhs)),
+      ValueKind::Sub(lhs, rhs, flags) =>
+        try!(write!(f, "sub {}{} {}", flags, lhs, rhs)),
 
-      ValueKind::Shl(lhs, rhs) => try!(write!(f, "shl {} {}", lhs, rhs)),
-      ValueKind::ZShr(lhs, rhs) => try!(write!(f, "zshr {} {}", lhs, rhs)), // zero-extend
-      ValueKind::SShr(lhs, rhs) => try!(write!(f, "sshr {} {}", lhs, rhs)), // sign-extend
+      ValueKind::Shl(lhs, rhs, flags) =>
+        try!(write!(f, "shl {}{} {}", flags, lhs, rhs)),
+      ValueKind::ZShr(lhs, rhs, exact) => {
+        try!(write!(f, "zshr "));
+        if exact {
+          try!(write!(f, "exact "));
+        }
+        try!(write!(f, "{} {}", lhs, rhs));
+      }
+      ValueKind::SShr(lhs, rhs, exact) => {
+        try!(write!(f, "sshr "));
+        if exact {
+          try!(write!(f, "exact "));
+        }
+        try!(write!(f, "{} {}", lhs, rhs));
+      }
 
       ValueKind::And(lhs, rhs) => try!(write!(f, "and {} {}", lhs, rhs)),
       ValueKind::Xor(lhs, rhs) => try!(write!(f, "xor {} {}", lhs, rhs)),
       ValueKind::Or(lhs, rhs) => try!(write!(f, "or {} {}", lhs, rhs)),
 
+      ValueKind::SAddOverflow(lhs, rhs) =>
+        try!(write!(f, "sadd_overflow {} {}", lhs, rhs)),
+      ValueKind::UAddOverflow(lhs, rhs) =>
+        try!(write!(f, "uadd_overflow {} {}", lhs, rhs)),
+      ValueKind::SSubOverflow(lhs, rhs) =>
+        try!(write!(f, "ssub_overflow {} {}", lhs, rhs)),
+      ValueKind::USubOverflow(lhs, rhs) =>
+        try!(write!(f, "usub_overflow {} {}", lhs, rhs)),
+      ValueKind::SMulOverflow(lhs, rhs) =>
+        try!(write!(f, "smul_overflow {} {}", lhs, rhs)),
+      ValueKind::UMulOverflow(lhs, rhs) =>
+        try!(write!(f, "umul_overflow {} {}", lhs, rhs)),
+      ValueKind::SAddSat(lhs, rhs) =>
+        try!(write!(f, "sadd_sat {} {}", lhs, rhs)),
+      ValueKind::UAddSat(lhs, rhs) =>
+        try!(write!(f, "uadd_sat {} {}", lhs, rhs)),
+      ValueKind::SSubSat(lhs, rhs) =>
+        try!(write!(f, "ssub_sat {} {}", lhs, rhs)),
+      ValueKind::USubSat(lhs, rhs) =>
+        try!(write!(f, "usub_sat {} {}", lhs, rhs)),
+
+      ValueKind::Ctpop(inner) => try!(write!(f, "ctpop {}", inner)),
+      ValueKind::Ctlz(inner) => try!(write!(f, "ctlz {}", inner)),
+      ValueKind::Cttz(inner) => try!(write!(f, "cttz {}", inner)),
+      ValueKind::Bswap(inner) => try!(write!(f, "bswap {}", inner)),
+      ValueKind::BitReverse(inner) =>
+        try!(write!(f, "bitreverse {}", inner)),
+      ValueKind::Fshl {
+        hi,
+        lo,
+        amount,
+      } => try!(write!(f, "fshl {} {} {}", hi, lo, amount)),
+      ValueKind::Fshr {
+        hi,
+        lo,
+        amount,
+      } => try!(write!(f, "fshr {} {} {}", hi, lo, amount)),
+
       ValueKind::Eq(lhs, rhs) => try!(write!(f, "eq {} {}", lhs, rhs)),
       ValueKind::Neq(lhs, rhs) => try!(write!(f, "neq {} {}", lhs, rhs)),
-      ValueKind::Lt(lhs, rhs) => try!(write!(f, "lt {} {}", lhs, rhs)),
-      ValueKind::Gt(lhs, rhs) => try!(write!(f, "gt {} {}", lhs, rhs)),
-      ValueKind::Lte(lhs, rhs) => try!(write!(f, "lte {} {}", lhs, rhs)),
-      ValueKind::Gte(lhs, rhs) => try!(write!(f, "gte {} {}", lhs, rhs)),
+      ValueKind::ULt(lhs, rhs) => try!(write!(f, "ult {} {}", lhs, rhs)),
+      ValueKind::SLt(lhs, rhs) => try!(write!(f, "slt {} {}", lhs, rhs)),
+      ValueKind::UGt(lhs, rhs) => try!(write!(f, "ugt {} {}", lhs, rhs)),
+      ValueKind::SGt(lhs, rhs) => try!(write!(f, "sgt {} {}", lhs, rhs)),
+      ValueKind::ULte(lhs, rhs) => try!(write!(f, "ulte {} {}", lhs, rhs)),
+      ValueKind::SLte(lhs, rhs) => try!(write!(f, "slte {} {}", lhs, rhs)),
+      ValueKind::UGte(lhs, rhs) => try!(write!(f, "ugte {} {}", lhs, rhs)),
+      ValueKind::SGte(lhs, rhs) => try!(write!(f, "sgte {} {}", lhs, rhs)),
+
+      ValueKind::FAdd(lhs, rhs) => try!(write!(f, "fadd {} {}", lhs, rhs)),
+      ValueKind::FSub(lhs, rhs) => try!(write!(f, "fsub {} {}", lhs, rhs)),
+      ValueKind::FMul(lhs, rhs) => try!(write!(f, "fmul {} {}", lhs, rhs)),
+      ValueKind::FDiv(lhs, rhs) => try!(write!(f, "fdiv {} {}", lhs, rhs)),
+      ValueKind::FRem(lhs, rhs) => try!(write!(f, "frem {} {}", lhs, rhs)),
+
+      ValueKind::FOEq(lhs, rhs) => try!(write!(f, "foeq {} {}", lhs, rhs)),
+      ValueKind::FONeq(lhs, rhs) => try!(write!(f, "foneq {} {}", lhs, rhs)),
+      ValueKind::FOLt(lhs, rhs) => try!(write!(f, "folt {} {}", lhs, rhs)),
+      ValueKind::FOGt(lhs, rhs) => try!(write!(f, "fogt {} {}", lhs, rhs)),
+      ValueKind::FOLte(lhs, rhs) => try!(write!(f, "folte {} {}", lhs, rhs)),
+      ValueKind::FOGte(lhs, rhs) => try!(write!(f, "fogte {} {}", lhs, rhs)),
+
+      ValueKind::FUEq(lhs, rhs) => try!(write!(f, "fueq {} {}", lhs, rhs)),
+      ValueKind::FUNeq(lhs, rhs) => try!(write!(f, "funeq {} {}", lhs, rhs)),
+      ValueKind::FULt(lhs, rhs) => try!(write!(f, "fult {} {}", lhs, rhs)),
+      ValueKind::FUGt(lhs, rhs) => try!(write!(f, "fugt {} {}", lhs, rhs)),
+      ValueKind::FULte(lhs, rhs) => try!(write!(f, "fulte {} {}", lhs, rhs)),
+      ValueKind::FUGte(lhs, rhs) => try!(write!(f, "fugte {} {}", lhs, rhs)),
+
+      ValueKind::FToUI {
+        value,
+        ty,
+      } => try!(write!(f, "ftoui {} {}", value, ty)),
+      ValueKind::FToSI {
+        value,
+        ty,
+      } => try!(write!(f, "ftosi {} {}", value, ty)),
+      ValueKind::UIToF {
+        value,
+        ty,
+      } => try!(write!(f, "uitof {} {}", value, ty)),
+      ValueKind::SIToF {
+        value,
+        ty,
+      } => try!(write!(f, "sitof {} {}", value, ty)),
 
-      ValueKind::Parameter(_) => panic!("pcb_ice: Parameters should not be \
-        displayed"),
+      ValueKind::Trunc {
+        value,
+        ty,
+      } => try!(write!(f, "trunc {} {}", value, ty)),
+      ValueKind::ZExt {
+        value,
+        ty,
+      } => try!(write!(f, "zext {} {}", value, ty)),
+      ValueKind::SExt {
+        value,
+        ty,
+      } => try!(write!(f, "sext {} {}", value, ty)),
+      ValueKind::Bitcast {
+        value,
+        ty,
+      } => try!(write!(f, "bitcast {} {}", value, ty)),
+
+      ValueKind::Select {
+        cond,
+        then,
+        else_,
+      } => try!(write!(f, "select {} {} {}", cond, then, else_)),
+
+      ValueKind::Parameter(_) | ValueKind::BlockParameter(_) =>
+        panic!("pcb_ice: Parameters should not be displayed"),
     }
     Ok(())
   }
@@ -267,6 +1351,9 @@ impl<'c> Debug for Value<'c> {
 
 impl<'c> Display for Value<'c> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    write!(f, "%{}", self.number)
+    match *self.name.borrow() {
+      Some(ref name) => write!(f, "%{}", name),
+      None => write!(f, "%{}", self.number),
+    }
   }
 }
@@ -0,0 +1,109 @@
+use common::Context;
+use function::{fmt_symbol_attributes, Function, Linkage, Visibility};
+use ty;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::cell::Cell;
+
+pub type GlobalContext<'c> = Context<Global<'c>>;
+
+pub struct Global<'c> {
+  pub name: String,
+  pub ty: &'c ty::Type,
+  // if there's no initializer, the global is zero-initialized
+  pub init: Option<Constant<'c>>,
+  // constant globals are read-only
+  pub constant: bool,
+  pub linkage: Cell<Linkage>,
+  pub visibility: Cell<Visibility>,
+}
+
+impl<'c> Display for Global<'c> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    if self.constant {
+      try!(write!(f, "constant "));
+    } else {
+      try!(write!(f, "global "));
+    }
+    try!(fmt_symbol_attributes(f, self.linkage.get(), self.visibility.get()));
+    try!(write!(f, "{}: {}", self.name, self.ty));
+    match self.init {
+      Some(ref init) => write!(f, " = {}", init),
+      None => write!(f, " = zeroed"),
+    }
+  }
+}
+
+impl<'c> PartialEq for Global<'c> {
+  fn eq(&self, rhs: &Self) -> bool {
+    self.name == rhs.name
+  }
+}
+impl<'c> Eq for Global<'c> { }
+impl<'c> Hash for Global<'c> {
+  fn hash<H>(&self, state: &mut H) where H: Hasher {
+    self.name.hash(state)
+  }
+}
+
+// module-level constant data, used to initialize globals
+pub struct Constant<'c> {
+  pub ty: &'c ty::Type,
+  pub kind: ConstantKind<'c>,
+}
+
+pub enum ConstantKind<'c> {
+  Int(u64),
+  Float(f64),
+  Bool(bool),
+  Aggregate(Box<[Constant<'c>]>),
+  Array(Box<[Constant<'c>]>),
+  // a byte-string literal; of type [i8; n]
+  Bytes(Box<[u8]>),
+  // these are of pointer and function pointer type, respectively
+  GlobalAddress(&'c Global<'c>),
+  FunctionAddress(&'c Function<'c>),
+}
+
+impl<'c> Display for Constant<'c> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match self.kind {
+      ConstantKind::Int(value) => write!(f, "{}", value),
+      ConstantKind::Float(value) => write!(f, "{:?}", value),
+      ConstantKind::Bool(value) => write!(f, "{}", value),
+      ConstantKind::Aggregate(ref fields) => {
+        try!(write!(f, "("));
+        if !fields.is_empty() {
+          for field in &fields[..fields.len() - 1] {
+            try!(write!(f, "{}, ", field));
+          }
+          try!(write!(f, "{}", fields[fields.len() - 1]));
+        }
+        write!(f, ")")
+      }
+      ConstantKind::Array(ref elements) => {
+        try!(write!(f, "["));
+        if !elements.is_empty() {
+          for element in &elements[..elements.len() - 1] {
+            try!(write!(f, "{}, ", element));
+          }
+          try!(write!(f, "{}", elements[elements.len() - 1]));
+        }
+        write!(f, "]")
+      }
+      ConstantKind::Bytes(ref bytes) => {
+        try!(write!(f, "b\""));
+        for &byte in bytes.iter() {
+          for c in ::std::ascii::escape_default(byte) {
+            try!(write!(f, "{}", c as char));
+          }
+        }
+        write!(f, "\"")
+      }
+      ConstantKind::GlobalAddress(global) =>
+        write!(f, "global_address {}", global.name),
+      ConstantKind::FunctionAddress(function) =>
+        write!(f, "function_address {}", function.name),
+    }
+  }
+}
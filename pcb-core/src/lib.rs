@@ -2,6 +2,8 @@ extern crate typed_arena;
 
 pub mod pcb;
 pub mod function;
+pub mod global;
 pub mod ty;
 pub mod common;
+pub mod debug;
 pub mod backend;
@@ -1,25 +1,39 @@
 use {std, ty};
+use debug::{FileContext, SourceFile};
 use function::{Function, FuncContext};
+use global::{Constant, Global, GlobalContext};
 
 pub struct Ctxt {
-  pub type_ctxt: ty::TypeContext,
+  // boxed so that functions can keep a reference to it across moves of the
+  // Ctxt
+  pub type_ctxt: Box<ty::TypeContext>,
   pub func_ctxt: FuncContext<'static>, // 'self
+  pub global_ctxt: GlobalContext<'static>, // 'self
+  pub file_ctxt: FileContext,
   pub optimize: bool,
+  // whether to emit debug info from the source locations
+  pub debug_info: bool,
 }
 
 impl Ctxt {
   pub fn new(opt: bool) -> Self {
     Ctxt {
-      type_ctxt: ty::TypeContext::new(),
+      type_ctxt: Box::new(ty::TypeContext::new()),
       func_ctxt: FuncContext::new(),
+      global_ctxt: GlobalContext::new(),
+      file_ctxt: FileContext::new(),
       optimize: opt,
+      debug_info: false,
     }
   }
 
-  pub fn add_function<'c>(&'c self, name: &str, ty: ty::Function<'c>)
-      -> &'c Function<'c> {
+  pub fn add_function<'c>(&'c self, name: &str, ty: ty::Function<'c>,
+      declaration: bool) -> &'c Function<'c> {
     use std::mem::transmute;
-    use function::{Value, ValueKind, ValueContext, BlockContext};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashSet;
+    use function::{Value, ValueKind, ValueContext, BlockContext, Linkage,
+      Visibility};
 
     let ret = unsafe {
       let ret = self.func_ctxt.push(Function {
@@ -27,6 +41,14 @@ impl Ctxt {
         ty: transmute::<ty::Function<'c>, ty::Function<'static>>(ty),
         values: ValueContext::new(),
         blocks: BlockContext::new(),
+        type_ctxt: transmute::<&'c ty::TypeContext, &'static ty::TypeContext>(
+          &*self.type_ctxt),
+        declaration: declaration,
+        linkage: Cell::new(Linkage::External),
+        visibility: Cell::new(Visibility::Default),
+        personality: Cell::new(None),
+        debug_info: RefCell::new(None),
+        names: RefCell::new(HashSet::new()),
       });
       transmute::<&'c Function<'static>, &'c Function<'c>>(ret)
     };
@@ -34,12 +56,41 @@ impl Ctxt {
       ret.values.push(Value {
         number: ret.values.len() as u32,
         kind: ValueKind::Parameter(param_ty),
+        loc: None,
+        name: RefCell::new(None),
         func: ret,
       });
     }
     ret
   }
 
+  pub fn add_global<'c>(&'c self, name: &str, ty: &'c ty::Type,
+      init: Option<Constant<'c>>, constant: bool) -> &'c Global<'c> {
+    use std::mem::transmute;
+    use std::cell::Cell;
+    use function::{Linkage, Visibility};
+
+    unsafe {
+      let ret = self.global_ctxt.push(Global {
+        name: name.to_owned(),
+        ty: transmute::<&'c ty::Type, &'static ty::Type>(ty),
+        init: transmute::<Option<Constant<'c>>, Option<Constant<'static>>>(
+          init),
+        constant: constant,
+        linkage: Cell::new(Linkage::External),
+        visibility: Cell::new(Visibility::Default),
+      });
+      transmute::<&'c Global<'static>, &'c Global<'c>>(ret)
+    }
+  }
+
+  pub fn add_file(&self, name: &str, directory: &str) -> &SourceFile {
+    self.file_ctxt.push(SourceFile {
+      name: name.to_owned(),
+      directory: directory.to_owned(),
+    })
+  }
+
   pub fn get_type(&self, ty: ty::Type) -> &ty::Type {
     self.type_ctxt.get(ty)
   }
@@ -47,6 +98,9 @@ impl Ctxt {
 
 impl std::fmt::Display for Ctxt {
   fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+    for global in &self.global_ctxt {
+      try!(writeln!(f, "{}", global));
+    }
     for func in &self.func_ctxt {
       try!(writeln!(f, "{}", func));
     }
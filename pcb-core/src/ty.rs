@@ -7,39 +7,199 @@ impl Type {
   pub fn int_size(&self) -> u32 {
     match *self {
       Type::Integer(size) => size,
+      _ => panic!("pcb_ice: attempted to get the size of a non-integer type"),
     }
   }
+
+  pub fn is_int(&self) -> bool {
+    if let Type::Integer(_) = *self {
+      true
+    } else {
+      false
+    }
+  }
+
+  pub fn float_size(&self) -> u32 {
+    match *self {
+      Type::Float(size) => size,
+      _ => panic!("pcb_ice: attempted to get the size of a non-float type"),
+    }
+  }
+
+  pub fn is_float(&self) -> bool {
+    if let Type::Float(_) = *self {
+      true
+    } else {
+      false
+    }
+  }
+
+  pub fn fn_ptr_output(&self) -> &Type {
+    match *self {
+      Type::FnPtr(_, ref output, _, _) => output,
+      _ => panic!("pcb_ice: attempted to get the output of a non-function \
+        pointer type"),
+    }
+  }
+
+  pub fn fn_ptr_inputs(&self) -> &[Type] {
+    match *self {
+      Type::FnPtr(ref inputs, _, _, _) => inputs,
+      _ => panic!("pcb_ice: attempted to get the inputs of a non-function \
+        pointer type"),
+    }
+  }
+
+  pub fn fn_ptr_variadic(&self) -> bool {
+    match *self {
+      Type::FnPtr(_, _, variadic, _) => variadic,
+      _ => panic!("pcb_ice: attempted to get the variadicness of a \
+        non-function pointer type"),
+    }
+  }
+
+  pub fn fn_ptr_call_conv(&self) -> CallConv {
+    match *self {
+      Type::FnPtr(_, _, _, call_conv) => call_conv,
+      _ => panic!("pcb_ice: attempted to get the calling convention of a \
+        non-function pointer type"),
+    }
+  }
+
+  pub fn is_fn_ptr(&self) -> bool {
+    if let Type::FnPtr(_, _, _, _) = *self {
+      true
+    } else {
+      false
+    }
+  }
+
+  pub fn aggregate_fields(&self) -> &[Type] {
+    match *self {
+      Type::Aggregate(ref fields) => fields,
+      _ => panic!("pcb_ice: attempted to get the fields of a non-aggregate \
+        type"),
+    }
+  }
+
+  pub fn is_aggregate(&self) -> bool {
+    if let Type::Aggregate(_) = *self {
+      true
+    } else {
+      false
+    }
+  }
+
+  pub fn array_element(&self) -> &Type {
+    match *self {
+      Type::Array(ref elem, _) => elem,
+      _ => panic!("pcb_ice: attempted to get the element type of a \
+        non-array type"),
+    }
+  }
+
+  pub fn array_len(&self) -> u64 {
+    match *self {
+      Type::Array(_, len) => len,
+      _ => panic!("pcb_ice: attempted to get the length of a non-array type"),
+    }
+  }
+
+  pub fn is_array(&self) -> bool {
+    if let Type::Array(_, _) = *self {
+      true
+    } else {
+      false
+    }
+  }
+
+  // the type of the exception caught by a landing pad: the exception object,
+  // and the selector identifying which catch matched
+  pub fn exception() -> Type {
+    Type::Aggregate(vec![Type::Pointer, Type::Integer(32)])
+  }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Type {
   Integer(u32),
-  /*
-  Void,
+  // either 32 or 64 bits
+  Float(u32),
   Bool,
+  Void,
+  // pointers are untyped; the type of the pointee is given when the pointer is
+  // used
   Pointer,
-  // FnPtr
-  Aggregate(Vec<Type<'c>>),
-  */
+  // a pointer to a function taking the inputs and returning the output; the
+  // bool is whether the function is variadic
+  FnPtr(Vec<Type>, Box<Type>, bool, CallConv),
+  Aggregate(Vec<Type>),
+  // element type, length
+  Array(Box<Type>, u64),
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Function<'t> {
   pub inputs: Box<[&'t Type]>,
   pub output: &'t Type,
+  // variadic functions take any number of extra arguments after the inputs
+  pub variadic: bool,
+  pub call_conv: CallConv,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum CallConv {
+  // the platform's C calling convention
+  C,
+  // as fast as possible; only for calls between pcb functions
+  Fast,
+  // for functions that are rarely called, like error paths; keeps as many
+  // registers as possible live across the call
+  Cold,
+}
+
+impl<'t> Function<'t> {
+  pub fn pointer_type(&self) -> Type {
+    let mut inputs = vec![];
+    for input in &self.inputs[..] {
+      inputs.push((*input).clone());
+    }
+    Type::FnPtr(inputs, Box::new(self.output.clone()), self.variadic,
+      self.call_conv)
+  }
 }
 
 mod fmt {
   use std::fmt::{Display, Formatter, Error};
-  use super::{Type, Function};
+  use super::{Type, Function, CallConv};
   impl Display for Type {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
       match *self {
         Type::Integer(n) => write!(f, "i{}", n),
-        /*
-        TypeVariant::Bool => write!(f, "bool"),
-        TypeVariant::Pointer => write!(f, "ptr"),
-        TypeVariant::Aggregate(ref v) => {
+        Type::Float(n) => write!(f, "f{}", n),
+        Type::Bool => write!(f, "bool"),
+        Type::Void => write!(f, "void"),
+        Type::Pointer => write!(f, "ptr"),
+        Type::FnPtr(ref inputs, ref output, variadic, call_conv) => {
+          if call_conv != CallConv::C {
+            try!(write!(f, "{} ", call_conv));
+          }
+          try!(write!(f, "fn("));
+          if !inputs.is_empty() {
+            for input in &inputs[..inputs.len() - 1] {
+              try!(write!(f, "{}, ", input));
+            }
+            try!(write!(f, "{}", inputs[inputs.len() - 1]));
+            if variadic {
+              try!(write!(f, ", "));
+            }
+          }
+          if variadic {
+            try!(write!(f, "..."));
+          }
+          write!(f, ") -> {}", output)
+        }
+        Type::Aggregate(ref v) => {
           try!(write!(f, "("));
           if v.is_empty() {
             write!(f, ")")
@@ -50,7 +210,17 @@ mod fmt {
             write!(f, "{})", &v[v.len() - 1])
           }
         }
-        */
+        Type::Array(ref elem, len) => write!(f, "[{}; {}]", elem, len),
+      }
+    }
+  }
+
+  impl Display for CallConv {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+      match *self {
+        CallConv::C => write!(f, "ccc"),
+        CallConv::Fast => write!(f, "fastcc"),
+        CallConv::Cold => write!(f, "coldcc"),
       }
     }
   }
@@ -63,6 +233,12 @@ mod fmt {
           try!(write!(f, "{}, ", input));
         }
         try!(write!(f, "{}", self.inputs[self.inputs.len() - 1]));
+        if self.variadic {
+          try!(write!(f, ", "));
+        }
+      }
+      if self.variadic {
+        try!(write!(f, "..."));
       }
       write!(f, ") -> {}", self.output)
     }
@@ -0,0 +1,18 @@
+extern crate gcc;
+
+use std::process::Command;
+
+// builds src/shim.cpp against the same LLVM that llvm-sys links to, found
+// through llvm-config
+fn main() {
+  let output = Command::new("llvm-config").arg("--cxxflags").output()
+    .expect("could not run llvm-config");
+  let cxxflags = String::from_utf8(output.stdout)
+    .expect("llvm-config printed invalid utf-8");
+  let mut config = gcc::Config::new();
+  config.cpp(true).file("src/shim.cpp");
+  for flag in cxxflags.split_whitespace() {
+    config.flag(flag);
+  }
+  config.compile("libpcb_llvm_shim.a");
+}
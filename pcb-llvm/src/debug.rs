@@ -0,0 +1,159 @@
+use core::debug::{FileContext, SourceFile, SourceLoc};
+use core::function::Function;
+use core::ty::Type;
+use llvm;
+
+use std::cell::RefCell;
+
+// a compile unit for each source file; source files are compared by address
+pub struct DebugInfo {
+  files: Vec<(*const SourceFile, llvm::DIBuilder)>,
+  optimized: bool,
+}
+
+impl DebugInfo {
+  pub fn new(module: &llvm::Module, files: &FileContext, optimized: bool)
+      -> Self {
+    module.add_debug_info_version();
+    let mut builders = vec![];
+    for file in files {
+      builders.push((file as *const _,
+        llvm::DIBuilder::new(module, &file.name, &file.directory, optimized)));
+    }
+    DebugInfo {
+      files: builders,
+      optimized: optimized,
+    }
+  }
+
+  fn builder(&self, file: *const SourceFile) -> &llvm::DIBuilder {
+    &self.files.iter().find(|&&(f, _)| f == file)
+      .expect("pcb_ice: source file from another context").1
+  }
+
+  // builds the subprogram of `func` and its parameter variables, whose
+  // values are recorded at the end of `entry`. a function with locations but
+  // no debug info is given a subprogram named after its symbol, at its first
+  // location; a function with neither has no debug info
+  pub fn build_function<'c>(&self, func: &Function<'c>, llfunc: llvm::Value,
+      entry: llvm::BasicBlock, target_data: &llvm::TargetData)
+      -> Option<FunctionScope> {
+    let debug_info = func.debug_info.borrow();
+    let (name, file, line) = match *debug_info {
+      Some(ref info) => (&info.name[..], info.file, info.line),
+      None => match first_location(func) {
+        Some(loc) => (&func.name[..], loc.file, loc.line),
+        None => return None,
+      },
+    };
+    let dibuilder = self.builder(file);
+    let mut types = vec![debug_type(dibuilder, target_data, func.ty.output)];
+    for input in func.ty.inputs.iter() {
+      types.push(debug_type(dibuilder, target_data, input));
+    }
+    let subprogram =
+      dibuilder.function(llfunc, name, line, &types, self.optimized);
+    if let Some(ref info) = *debug_info {
+      for (i, param_name) in info.param_names.iter().enumerate() {
+        dibuilder.parameter(subprogram, param_name, i as u32 + 1,
+          types[i + 1], llvm::Value::get_param(llfunc, i as u32), entry);
+      }
+    }
+    Some(FunctionScope {
+      debug_info: self,
+      subprogram: subprogram,
+      file: file,
+      file_scopes: RefCell::new(vec![]),
+    })
+  }
+}
+
+pub struct FunctionScope<'d> {
+  debug_info: &'d DebugInfo,
+  subprogram: llvm::Metadata,
+  file: *const SourceFile,
+  // for locations in files other than the function's
+  file_scopes: RefCell<Vec<(*const SourceFile, llvm::Metadata)>>,
+}
+
+impl<'d> FunctionScope<'d> {
+  // gives `loc` to everything `builder` builds from now on. LLVM requires
+  // calls in a function with debug info to have a location, so line 0, for
+  // "no location", is used if there's none
+  pub fn set_location(&self, builder: &llvm::Builder,
+      loc: Option<SourceLoc>) {
+    match loc {
+      Some(loc) => {
+        builder.set_debug_location(loc.line, loc.column, self.scope(loc.file))
+      }
+      None => builder.set_debug_location(0, 0, self.subprogram),
+    }
+  }
+
+  fn scope(&self, file: *const SourceFile) -> llvm::Metadata {
+    if file == self.file {
+      return self.subprogram;
+    }
+    let mut file_scopes = self.file_scopes.borrow_mut();
+    if let Some(&(_, scope)) = file_scopes.iter().find(|&&(f, _)| f == file) {
+      return scope;
+    }
+    let scope = self.debug_info.builder(self.file).lexical_block_file(
+      self.subprogram, self.debug_info.builder(file).file());
+    file_scopes.push((file, scope));
+    scope
+  }
+}
+
+fn first_location<'c>(func: &Function<'c>) -> Option<SourceLoc<'c>> {
+  for blk in &func.blocks {
+    for value in blk.block_values.borrow().iter() {
+      if value.loc.is_some() {
+        return value.loc;
+      }
+    }
+    if blk.terminator_loc.get().is_some() {
+      return blk.terminator_loc.get();
+    }
+  }
+  None
+}
+
+// pcb integers have no signedness, so they're shown as signed
+fn debug_type(dibuilder: &llvm::DIBuilder, target_data: &llvm::TargetData,
+    ty: &Type) -> llvm::Metadata {
+  let llty = llvm::get_type(target_data, ty);
+  match *ty {
+    Type::Void => llvm::Metadata::null(),
+    Type::Integer(_) => {
+      dibuilder.basic_type(&ty.to_string(),
+        target_data.store_size_in_bits(llty), llvm::DW_ATE_SIGNED)
+    }
+    Type::Float(_) => {
+      dibuilder.basic_type(&ty.to_string(),
+        target_data.store_size_in_bits(llty), llvm::DW_ATE_FLOAT)
+    }
+    Type::Bool => {
+      dibuilder.basic_type(&ty.to_string(),
+        target_data.store_size_in_bits(llty), llvm::DW_ATE_BOOLEAN)
+    }
+    Type::Pointer | Type::FnPtr(..) => {
+      dibuilder.pointer_type(target_data.pointer_size_in_bits())
+    }
+    Type::Aggregate(ref fields) => {
+      let mut di_fields = vec![];
+      let mut offsets = vec![];
+      for (i, field) in fields.iter().enumerate() {
+        di_fields.push(debug_type(dibuilder, target_data, field));
+        offsets.push(target_data.offset_of_field_in_bits(llty, i as u32));
+      }
+      dibuilder.struct_type(target_data.size_in_bits(llty),
+        target_data.align_in_bits(llty), &di_fields, &offsets)
+    }
+    Type::Array(ref element, len) => {
+      dibuilder.array_type(target_data.size_in_bits(llty),
+        target_data.align_in_bits(llty),
+        debug_type(dibuilder, target_data, element), len)
+    }
+  }
+}
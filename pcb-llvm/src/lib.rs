@@ -3,9 +3,11 @@ extern crate pcb_core as core;
 use core::pcb::Ctxt;
 use core::backend::Backend;
 use core::function::{Block, Function, Value, Terminator};
+use core::global::{Constant, ConstantKind, Global};
 
 use std::collections::HashMap;
 
+mod debug;
 mod llvm;
 
 pub struct Llvm;
@@ -22,18 +24,62 @@ impl Backend for Llvm {
     };
     let target_machine = llvm::TargetMachine::new(opt_level).unwrap();
     let target_data = llvm::TargetData::from_target_machine(&target_machine);
+    let debug_info = if ctxt.debug_info {
+      Some(debug::DebugInfo::new(&module, &ctxt.file_ctxt, ctxt.optimize))
+    } else {
+      None
+    };
 
     //let _optimizer = llvm::FnOptimizer::for_module(&module);
 
     for function in &ctxt.func_ctxt {
-      function_hm.insert(function,
-        module.add_function(&function.name,
-          llvm::get_function_type(&target_data, function.ty())));
+      let llfunc = module.add_function(&function.name,
+        llvm::get_function_type(&target_data, function.ty()));
+      llvm::set_linkage(llfunc, function.linkage.get(), function.declaration);
+      llvm::set_visibility(llfunc, function.visibility.get());
+      llvm::set_function_call_conv(llfunc, function.ty.call_conv);
+      function_hm.insert(function, llfunc);
+    }
+    let mut global_hm = HashMap::new();
+    for global in &ctxt.global_ctxt {
+      let llglobal = module.add_global(&global.name,
+        llvm::get_type(&target_data, global.ty));
+      llvm::set_linkage(llglobal, global.linkage.get(), false);
+      llvm::set_visibility(llglobal, global.visibility.get());
+      global_hm.insert(global, llglobal);
     }
+    // initializers may refer to any function or global, so they're only set
+    // once everything has been added
+    for global in &ctxt.global_ctxt {
+      let llglobal = *global_hm.get(&global).expect("pcb_ice: blorghle");
+      let init = match global.init {
+        Some(ref init) => {
+          build_constant(init, &function_hm, &global_hm, &target_data)
+        }
+        None => {
+          llvm::Value::const_null(llvm::get_type(&target_data, global.ty))
+        }
+      };
+      llvm::set_initializer(llglobal, init);
+      llvm::set_global_constant(llglobal, global.constant);
+    }
+
     for function in &ctxt.func_ctxt {
+      if let Some(personality) = function.personality.get() {
+        llvm::set_personality(
+          *function_hm.get(&function).expect("pcb_ice: blorghle"),
+          *function_hm.get(&personality).expect("pcb_ice: blorghle"));
+      }
+    }
+
+    // declarations are left as external declarations, without a body
+    for function in ctxt.func_ctxt.iter().filter(|f| !f.declaration) {
       build_function(function,
-        *function_hm.get(&function).expect("pcb_ice: blorghle"), &function_hm);
+        *function_hm.get(&function).expect("pcb_ice: blorghle"), &function_hm,
+        &global_hm, &target_data, debug_info.as_ref());
     }
+    // finalizes the compile units
+    drop(debug_info);
 
     if print_llvm_ir {
       module.dump();
@@ -48,40 +94,176 @@ impl Backend for Llvm {
   }
 }
 
+fn build_constant<'a>(constant: &Constant<'a>,
+    functions: &HashMap<&Function<'a>, llvm::Value>,
+    globals: &HashMap<&Global<'a>, llvm::Value>,
+    target_data: &llvm::TargetData) -> llvm::Value {
+  match constant.kind {
+    ConstantKind::Int(value) => {
+      llvm::Value::const_int(llvm::get_int_type(constant.ty.int_size()), value)
+    }
+    ConstantKind::Float(value) => {
+      llvm::Value::const_float(llvm::get_type(target_data, constant.ty), value)
+    }
+    ConstantKind::Bool(value) => llvm::Value::const_bool(value),
+    ConstantKind::Aggregate(ref fields) => {
+      let mut llvm_fields = vec![];
+      for field in fields.iter() {
+        llvm_fields.push(
+          build_constant(field, functions, globals, target_data));
+      }
+      llvm::Value::const_struct(&llvm_fields)
+    }
+    ConstantKind::Array(ref elements) => {
+      let mut llvm_elements = vec![];
+      for element in elements.iter() {
+        llvm_elements.push(
+          build_constant(element, functions, globals, target_data));
+      }
+      llvm::Value::const_array(
+        llvm::get_type(target_data, constant.ty.array_element()),
+        &llvm_elements)
+    }
+    ConstantKind::Bytes(ref bytes) => llvm::Value::const_bytes(bytes),
+    ConstantKind::GlobalAddress(global) => {
+      llvm::Value::const_bitcast(
+        *globals.get(global).expect("pcb_ice: Blorghle"),
+        llvm::get_type(target_data, constant.ty))
+    }
+    ConstantKind::FunctionAddress(function) => {
+      *functions.get(function).expect("pcb_ice: Blorghle")
+    }
+  }
+}
+
 fn build_function<'a>(func: &Function<'a>, llfunc: llvm::Value,
-    functions: &HashMap<&Function<'a>, llvm::Value>) {
+    functions: &HashMap<&Function<'a>, llvm::Value>,
+    globals: &HashMap<&Global<'a>, llvm::Value>,
+    target_data: &llvm::TargetData, debug_info: Option<&debug::DebugInfo>) {
   let mut llvm_blocks = vec![];
-  let mut llvm_values = vec![];
+  // indexed by value number; the blocks are built in reverse postorder, so
+  // every value is filled in before it's used
+  let mut llvm_values = vec![llvm::Value::null(); func.values.len()];
 
   if func.blocks.iter().next().is_none() {
     panic!("pcb_assert: function {} has no associated blocks", func.name)
   }
   for i in 0..func.ty.inputs.len() {
-    llvm_values.push(llvm::Value::get_param(llfunc, i as u32))
+    llvm_values[i] = llvm::Value::get_param(llfunc, i as u32);
+    set_name(func.values.get(i).expect("pcb_ice: parameters"),
+      llvm_values[i]);
   }
   let builder = llvm::Builder::new();
-  for i in 0..func.blocks.len() {
-    llvm_blocks.push(llvm::BasicBlock::append(llfunc, i as u32));
+  for block in &func.blocks {
+    llvm_blocks.push(llvm::BasicBlock::append(llfunc, &block.to_string()));
   }
+  let scope = debug_info.and_then(|debug_info|
+    debug_info.build_function(func, llfunc, llvm_blocks[0], target_data));
 
+  // block parameters become phi nodes; they're built before anything else so
+  // that blocks which haven't been built yet can still be referred to
   for (i, block) in func.blocks.iter().enumerate() {
     builder.position_at_end(llvm_blocks[i]);
-    build_block(block, &builder, functions, &llvm_blocks, &mut llvm_values);
+    for param in block.params.iter() {
+      llvm_values[param.number as usize] =
+        builder.build_phi(llvm::get_type(target_data, param.ty()));
+      set_name(param, llvm_values[param.number as usize]);
+    }
+  }
+
+  for i in block_order(func) {
+    let block = func.blocks.get(i).expect("pcb_ice: block order");
+    builder.position_at_end(llvm_blocks[i]);
+    build_block(block, &builder, functions, globals, target_data,
+      &llvm_blocks, &mut llvm_values, scope.as_ref());
+  }
+
+  // the incoming values of a phi may come from anywhere in the function, so
+  // they can only be added once everything else has been built
+  for (i, block) in func.blocks.iter().enumerate() {
+    match *block.terminator.borrow() {
+      Terminator::Branch(to, ref args) => {
+        for (param, arg) in to.params.iter().zip(args.iter()) {
+          llvm::add_incoming(llvm_values[param.number as usize],
+            llvm_values[arg.number as usize], llvm_blocks[i]);
+        }
+      }
+      // the result of an invoke is passed to its normal destination
+      Terminator::Invoke {
+        normal,
+        ..
+      } => {
+        if let Some(param) = normal.params.first() {
+          llvm::add_incoming(llvm_values[param.number as usize],
+            llvm_blocks[i].get_terminator(), llvm_blocks[i]);
+        }
+      }
+      _ => {}
+    }
   }
 }
 
+fn set_name(value: &Value, llval: llvm::Value) {
+  if let Some(ref name) = *value.name.borrow() {
+    llvm::set_value_name(llval, name);
+  }
+}
+
+// reverse postorder from the entry block, so that blocks are built after the
+// blocks that dominate them; unreachable blocks are built last
+fn block_order(func: &Function) -> Vec<usize> {
+  let mut visited = vec![false; func.blocks.len()];
+  let mut order = vec![];
+  // (block, whether its successors have already been visited)
+  let mut stack = vec![(0, false)];
+  while let Some((blk, done)) = stack.pop() {
+    if done {
+      order.push(blk);
+      continue;
+    }
+    if visited[blk] {
+      continue;
+    }
+    visited[blk] = true;
+    stack.push((blk, true));
+    let block = func.blocks.get(blk).expect("pcb_ice: block order");
+    for succ in block.terminator.borrow().successors() {
+      if !visited[succ.number as usize] {
+        stack.push((succ.number as usize, false));
+      }
+    }
+  }
+  order.reverse();
+  for (i, &visited) in visited.iter().enumerate() {
+    if !visited {
+      order.push(i);
+    }
+  }
+  order
+}
+
 fn build_block<'a>(blk: &Block<'a>, builder: &llvm::Builder,
     functions: &HashMap<&Function<'a>, llvm::Value>,
-    blocks: &[llvm::BasicBlock], values: &mut Vec<llvm::Value>) {
+    globals: &HashMap<&Global<'a>, llvm::Value>,
+    target_data: &llvm::TargetData, blocks: &[llvm::BasicBlock],
+    values: &mut [llvm::Value], scope: Option<&debug::FunctionScope>) {
   for value in &*blk.block_values.borrow() {
-    build_value(value, builder, functions, values);
+    if let Some(scope) = scope {
+      scope.set_location(builder, value.loc);
+    }
+    build_value(value, builder, functions, globals, target_data, values);
+  }
+  if let Some(scope) = scope {
+    scope.set_location(builder, blk.terminator_loc.get());
   }
-  build_terminator(blk.terminator.get(), &builder, blocks, values);
+  build_terminator(&*blk.terminator.borrow(), &builder, functions, blocks,
+    values);
 }
 
 fn build_value<'a>(value: &Value<'a>, builder: &llvm::Builder,
     functions: &HashMap<&Function<'a>, llvm::Value>,
-    values: &mut Vec<llvm::Value>) {
+    globals: &HashMap<&Global<'a>, llvm::Value>,
+    target_data: &llvm::TargetData, values: &mut [llvm::Value]) {
   use core::function::ValueKind;
   let llval = match value.kind {
     ValueKind::ConstInt {
@@ -90,28 +272,249 @@ fn build_value<'a>(value: &Value<'a>, builder: &llvm::Builder,
     } => {
       llvm::Value::const_int(llvm::get_int_type(ty.int_size()), value)
     }
+    ValueKind::ConstFloat {
+      ty,
+      value,
+    } => {
+      llvm::Value::const_float(llvm::get_type(target_data, ty), value)
+    }
+    ValueKind::ConstBool(value) => llvm::Value::const_bool(value),
+    ValueKind::ConstAggregate {
+      ref fields,
+      ..
+    } => {
+      let mut llvm_fields = vec![];
+      for field in fields.iter() {
+        llvm_fields.push(values[field.number as usize]);
+      }
+      llvm::Value::const_struct(&llvm_fields)
+    }
+    ValueKind::ConstArray {
+      ty,
+      ref elements,
+    } => {
+      let mut llvm_elements = vec![];
+      for element in elements.iter() {
+        llvm_elements.push(values[element.number as usize]);
+      }
+      llvm::Value::const_array(
+        llvm::get_type(target_data, ty.array_element()), &llvm_elements)
+    }
     ValueKind::Call {
       function,
-      ref parameters
+      ref parameters,
+      tail,
     } => {
       let mut llvm_params = vec![];
       for param in parameters.iter() {
         llvm_params.push(values[param.number as usize]);
       }
-      builder.build_call(*functions.get(function).expect("pcb_ice: Blorghle"),
-        &llvm_params)
+      builder.build_call_with_conv(
+        *functions.get(function).expect("pcb_ice: Blorghle"), &llvm_params,
+        function.ty.call_conv, tail)
     }
-    ValueKind::Mul(lhs, rhs) => {
-      builder.build_mul(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::CallIndirect {
+      callee,
+      ref parameters,
+      tail,
+    } => {
+      let mut llvm_params = vec![];
+      for param in parameters.iter() {
+        llvm_params.push(values[param.number as usize]);
+      }
+      builder.build_call_with_conv(values[callee.number as usize],
+        &llvm_params, callee.ty().fn_ptr_call_conv(), tail)
     }
-    ValueKind::UDiv(lhs, rhs) => {
-      builder.build_udiv(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::InlineAsm {
+      ref template,
+      ref constraints,
+      side_effects,
+      align_stack,
+      ref operands,
+      ty,
+    } => {
+      let mut llvm_operands = vec![];
+      for operand in operands.iter() {
+        llvm_operands.push(values[operand.number as usize]);
+      }
+      builder.build_inline_asm(template, constraints, side_effects,
+        align_stack, llvm::get_type(target_data, ty), &llvm_operands)
     }
-    ValueKind::SDiv(lhs, rhs) => {
-      builder.build_sdiv(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::FunctionAddress(function) => {
+      *functions.get(function).expect("pcb_ice: Blorghle")
+    }
+    ValueKind::GlobalAddress(global) => {
+      builder.build_bitcast(*globals.get(global).expect("pcb_ice: Blorghle"),
+        llvm::get_type(target_data, value.ty()))
+    }
+    ValueKind::LandingPad {
+      cleanup,
+      ref catches,
+    } => {
+      let ptr_ty = llvm::get_type(target_data, &core::ty::Type::Pointer);
+      let mut clauses = vec![];
+      for catch in catches.iter() {
+        clauses.push(match *catch {
+          Some(global) => llvm::Value::const_bitcast(
+            *globals.get(global).expect("pcb_ice: Blorghle"), ptr_ty),
+          None => llvm::Value::const_null(ptr_ty),
+        });
+      }
+      let personality = value.func.personality.get()
+        .expect("pcb_ice: landing pad without a personality");
+      builder.build_landing_pad(llvm::get_type(target_data, value.ty()),
+        *functions.get(personality).expect("pcb_ice: Blorghle"), cleanup,
+        &clauses)
+    }
+    ValueKind::ExtractField {
+      value: aggregate,
+      index,
+    } => {
+      builder.build_extract_value(values[aggregate.number as usize], index)
+    }
+    ValueKind::InsertField {
+      value: aggregate,
+      index,
+      field,
+    } => {
+      builder.build_insert_value(values[aggregate.number as usize], index,
+        values[field.number as usize])
+    }
+
+    // pcb pointers are i8*, so they're cast to the real type on use
+    ValueKind::Alloca(ty) => {
+      let ptr = builder.build_alloca(llvm::get_type(target_data, ty), "");
+      builder.build_bitcast(ptr, llvm::get_type(target_data, value.ty()))
+    }
+    ValueKind::Load {
+      ptr,
+      ty,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, ty)));
+      builder.build_load(ptr)
+    }
+    ValueKind::Store {
+      ptr,
+      value: stored,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, stored.ty())));
+      builder.build_store(ptr, values[stored.number as usize]);
+      llvm::Value::null()
+    }
+    ValueKind::FieldAddress {
+      ptr,
+      ty,
+      index,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, ty)));
+      let field = builder.build_struct_gep(ptr, index);
+      builder.build_bitcast(field, llvm::get_type(target_data, value.ty()))
+    }
+    ValueKind::ElementAddress {
+      ptr,
+      ty,
+      index,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, ty)));
+      let zero = llvm::Value::const_int(llvm::get_int_type(32), 0);
+      let element =
+        builder.build_gep(ptr, &[zero, values[index.number as usize]]);
+      builder.build_bitcast(element, llvm::get_type(target_data, value.ty()))
+    }
+
+    ValueKind::AtomicLoad {
+      ptr,
+      ty,
+      ordering,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, ty)));
+      builder.build_atomic_load(ptr, ordering,
+        llvm::abi_align_of_type(target_data, ty))
+    }
+    ValueKind::AtomicStore {
+      ptr,
+      value: stored,
+      ordering,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, stored.ty())));
+      builder.build_atomic_store(ptr, values[stored.number as usize],
+        ordering, llvm::abi_align_of_type(target_data, stored.ty()));
+      llvm::Value::null()
+    }
+    ValueKind::AtomicRmw {
+      op,
+      ptr,
+      value: operand,
+      ordering,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, operand.ty())));
+      builder.build_atomic_rmw(op, ptr, values[operand.number as usize],
+        ordering)
+    }
+    ValueKind::CmpXchg {
+      ptr,
+      expected,
+      new,
+      success,
+      failure,
+    } => {
+      let ptr = builder.build_bitcast(values[ptr.number as usize],
+        llvm::get_pointer_type(llvm::get_type(target_data, expected.ty())));
+      builder.build_cmpxchg(ptr, values[expected.number as usize],
+        values[new.number as usize], success, failure)
+    }
+    ValueKind::Fence(ordering) => {
+      builder.build_fence(ordering);
+      llvm::Value::null()
+    }
+
+    // the layout of va_list is target-specific, so it's given 32 bytes of
+    // 8-aligned storage, which is enough for every common target
+    ValueKind::VaStart => {
+      let storage = core::ty::Type::Array(Box::new(core::ty::Type::Integer(64)),
+        4);
+      let list = builder.build_entry_alloca(
+        llvm::get_type(target_data, &storage), "");
+      let list = builder.build_bitcast(list,
+        llvm::get_type(target_data, value.ty()));
+      builder.build_intrinsic_call("llvm.va_start",
+        llvm::get_type(target_data, &core::ty::Type::Void), &[list]);
+      list
+    }
+    ValueKind::VaArg {
+      list,
+      ty,
+    } => {
+      builder.build_va_arg(values[list.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::VaEnd(list) => {
+      builder.build_intrinsic_call("llvm.va_end",
+        llvm::get_type(target_data, &core::ty::Type::Void),
+        &[values[list.number as usize]]);
+      llvm::Value::null()
+    }
+    ValueKind::Neg(inner) => builder.build_neg(values[inner.number as usize]),
+    ValueKind::Not(inner) => builder.build_not(values[inner.number as usize]),
+
+    ValueKind::Mul(lhs, rhs, flags) => {
+      llvm::set_wrap_flags(builder.build_mul(values[lhs.number as usize],
+        values[rhs.number as usize]), flags)
+    }
+    ValueKind::UDiv(lhs, rhs, exact) => {
+      llvm::set_exact(builder.build_udiv(values[lhs.number as usize],
+        values[rhs.number as usize]), exact)
+    }
+    ValueKind::SDiv(lhs, rhs, exact) => {
+      llvm::set_exact(builder.build_sdiv(values[lhs.number as usize],
+        values[rhs.number as usize]), exact)
     }
     ValueKind::URem(lhs, rhs) => {
       builder.build_urem(values[lhs.number as usize],
@@ -122,26 +525,26 @@ fn build_value<'a>(value: &Value<'a>, builder: &llvm::Builder,
         values[rhs.number as usize])
     }
 
-    ValueKind::Add(lhs, rhs) => {
-      builder.build_add(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::Add(lhs, rhs, flags) => {
+      llvm::set_wrap_flags(builder.build_add(values[lhs.number as usize],
+        values[rhs.number as usize]), flags)
     }
-    ValueKind::Sub(lhs, rhs) => {
-      builder.build_sub(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::Sub(lhs, rhs, flags) => {
+      llvm::set_wrap_flags(builder.build_sub(values[lhs.number as usize],
+        values[rhs.number as usize]), flags)
     }
 
-    ValueKind::Shl(lhs, rhs) => {
-      builder.build_shl(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::Shl(lhs, rhs, flags) => {
+      llvm::set_wrap_flags(builder.build_shl(values[lhs.number as usize],
+        values[rhs.number as usize]), flags)
     }
-    ValueKind::ZShr(lhs, rhs) => {
-      builder.build_ashr(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::ZShr(lhs, rhs, exact) => {
+      llvm::set_exact(builder.build_lshr(values[lhs.number as usize],
+        values[rhs.number as usize]), exact)
     }
-    ValueKind::SShr(lhs, rhs) => {
-      builder.build_lshr(values[lhs.number as usize],
-        values[rhs.number as usize])
+    ValueKind::SShr(lhs, rhs, exact) => {
+      llvm::set_exact(builder.build_ashr(values[lhs.number as usize],
+        values[rhs.number as usize]), exact)
     }
 
     ValueKind::And(lhs, rhs) => {
@@ -157,27 +560,434 @@ fn build_value<'a>(value: &Value<'a>, builder: &llvm::Builder,
         values[rhs.number as usize])
     }
 
-    ValueKind::Eq(_, _) => unimplemented!(),
-    ValueKind::Neq(_, _) => unimplemented!(),
-    ValueKind::Lt(_, _) => unimplemented!(),
-    ValueKind::Gt(_, _) => unimplemented!(),
-    ValueKind::Lte(_, _) => unimplemented!(),
-    ValueKind::Gte(_, _) => unimplemented!(),
-    ValueKind::Parameter(_) => panic!("pcb_ice: Parameter should never be \
-      built"),
+    ValueKind::SAddOverflow(lhs, rhs) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.sadd.with.overflow.i{}", lhs.ty().int_size()),
+        llvm::get_type(target_data, value.ty()),
+        &[values[lhs.number as usize], values[rhs.number as usize]])
+    }
+    ValueKind::UAddOverflow(lhs, rhs) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.uadd.with.overflow.i{}", lhs.ty().int_size()),
+        llvm::get_type(target_data, value.ty()),
+        &[values[lhs.number as usize], values[rhs.number as usize]])
+    }
+    ValueKind::SSubOverflow(lhs, rhs) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.ssub.with.overflow.i{}", lhs.ty().int_size()),
+        llvm::get_type(target_data, value.ty()),
+        &[values[lhs.number as usize], values[rhs.number as usize]])
+    }
+    ValueKind::USubOverflow(lhs, rhs) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.usub.with.overflow.i{}", lhs.ty().int_size()),
+        llvm::get_type(target_data, value.ty()),
+        &[values[lhs.number as usize], values[rhs.number as usize]])
+    }
+    ValueKind::SMulOverflow(lhs, rhs) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.smul.with.overflow.i{}", lhs.ty().int_size()),
+        llvm::get_type(target_data, value.ty()),
+        &[values[lhs.number as usize], values[rhs.number as usize]])
+    }
+    ValueKind::UMulOverflow(lhs, rhs) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.umul.with.overflow.i{}", lhs.ty().int_size()),
+        llvm::get_type(target_data, value.ty()),
+        &[values[lhs.number as usize], values[rhs.number as usize]])
+    }
+    // the saturating intrinsics are newer than the LLVM this backend supports,
+    // so they're built from the overflow intrinsics
+    ValueKind::SAddSat(lhs, rhs) => {
+      build_saturating(builder, target_data, "sadd", lhs, rhs, values)
+    }
+    ValueKind::UAddSat(lhs, rhs) => {
+      build_saturating(builder, target_data, "uadd", lhs, rhs, values)
+    }
+    ValueKind::SSubSat(lhs, rhs) => {
+      build_saturating(builder, target_data, "ssub", lhs, rhs, values)
+    }
+    ValueKind::USubSat(lhs, rhs) => {
+      build_saturating(builder, target_data, "usub", lhs, rhs, values)
+    }
+
+    ValueKind::Ctpop(inner) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.ctpop.i{}", inner.ty().int_size()),
+        llvm::get_type(target_data, inner.ty()),
+        &[values[inner.number as usize]])
+    }
+    // the second argument says whether the result is undefined for zero
+    ValueKind::Ctlz(inner) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.ctlz.i{}", inner.ty().int_size()),
+        llvm::get_type(target_data, inner.ty()),
+        &[values[inner.number as usize], llvm::Value::const_bool(false)])
+    }
+    ValueKind::Cttz(inner) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.cttz.i{}", inner.ty().int_size()),
+        llvm::get_type(target_data, inner.ty()),
+        &[values[inner.number as usize], llvm::Value::const_bool(false)])
+    }
+    ValueKind::Bswap(inner) => {
+      builder.build_intrinsic_call(
+        &format!("llvm.bswap.i{}", inner.ty().int_size()),
+        llvm::get_type(target_data, inner.ty()),
+        &[values[inner.number as usize]])
+    }
+    // llvm.bitreverse and llvm.fshl/fshr are newer than the LLVM this backend
+    // supports, so they're expanded
+    ValueKind::BitReverse(inner) => {
+      build_bitreverse(builder, inner, values)
+    }
+    ValueKind::Fshl {
+      hi,
+      lo,
+      amount,
+    } => {
+      build_funnel_shift(builder, target_data, true, hi, lo, amount, values)
+    }
+    ValueKind::Fshr {
+      hi,
+      lo,
+      amount,
+    } => {
+      build_funnel_shift(builder, target_data, false, hi, lo, amount, values)
+    }
+
+    ValueKind::Eq(lhs, rhs) => {
+      builder.build_icmp(llvm::IntEQ, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::Neq(lhs, rhs) => {
+      builder.build_icmp(llvm::IntNE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::ULt(lhs, rhs) => {
+      builder.build_icmp(llvm::IntULT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::SLt(lhs, rhs) => {
+      builder.build_icmp(llvm::IntSLT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::UGt(lhs, rhs) => {
+      builder.build_icmp(llvm::IntUGT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::SGt(lhs, rhs) => {
+      builder.build_icmp(llvm::IntSGT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::ULte(lhs, rhs) => {
+      builder.build_icmp(llvm::IntULE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::SLte(lhs, rhs) => {
+      builder.build_icmp(llvm::IntSLE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::UGte(lhs, rhs) => {
+      builder.build_icmp(llvm::IntUGE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::SGte(lhs, rhs) => {
+      builder.build_icmp(llvm::IntSGE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FAdd(lhs, rhs) => {
+      builder.build_fadd(values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FSub(lhs, rhs) => {
+      builder.build_fsub(values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FMul(lhs, rhs) => {
+      builder.build_fmul(values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FDiv(lhs, rhs) => {
+      builder.build_fdiv(values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FRem(lhs, rhs) => {
+      builder.build_frem(values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+
+    ValueKind::FOEq(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealOEQ, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FONeq(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealONE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FOLt(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealOLT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FOGt(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealOGT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FOLte(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealOLE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FOGte(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealOGE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FUEq(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealUEQ, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FUNeq(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealUNE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FULt(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealULT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FUGt(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealUGT, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FULte(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealULE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+    ValueKind::FUGte(lhs, rhs) => {
+      builder.build_fcmp(llvm::RealUGE, values[lhs.number as usize],
+        values[rhs.number as usize])
+    }
+
+    ValueKind::FToUI {
+      value,
+      ty,
+    } => {
+      builder.build_fp_to_ui(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::FToSI {
+      value,
+      ty,
+    } => {
+      builder.build_fp_to_si(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::UIToF {
+      value,
+      ty,
+    } => {
+      builder.build_ui_to_fp(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::SIToF {
+      value,
+      ty,
+    } => {
+      builder.build_si_to_fp(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+
+    ValueKind::Trunc {
+      value,
+      ty,
+    } => {
+      builder.build_trunc(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::ZExt {
+      value,
+      ty,
+    } => {
+      builder.build_zext(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::SExt {
+      value,
+      ty,
+    } => {
+      builder.build_sext(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+    ValueKind::Bitcast {
+      value,
+      ty,
+    } => {
+      builder.build_bitcast(values[value.number as usize],
+        llvm::get_type(target_data, ty))
+    }
+
+    ValueKind::Select {
+      cond,
+      then,
+      else_,
+    } => {
+      builder.build_select(values[cond.number as usize],
+        values[then.number as usize], values[else_.number as usize])
+    }
+
+    ValueKind::Parameter(_) | ValueKind::BlockParameter(_) =>
+      panic!("pcb_ice: Parameter should never be built"),
+  };
+  set_name(value, llval);
+  values[value.number as usize] = llval;
+}
+
+// `op` is one of sadd, uadd, ssub, or usub
+fn build_saturating<'a>(builder: &llvm::Builder,
+    target_data: &llvm::TargetData, op: &str, lhs: &Value<'a>,
+    rhs: &Value<'a>, values: &[llvm::Value]) -> llvm::Value {
+  use core::ty::Type;
+  let ty = llvm::get_type(target_data, lhs.ty());
+  let lllhs = values[lhs.number as usize];
+  let overflow_ty = Type::Aggregate(vec![lhs.ty().clone(), Type::Bool]);
+  let result = builder.build_intrinsic_call(
+    &format!("llvm.{}.with.overflow.i{}", op, lhs.ty().int_size()),
+    llvm::get_type(target_data, &overflow_ty),
+    &[lllhs, values[rhs.number as usize]]);
+  let wrapped = builder.build_extract_value(result, 0);
+  let overflowed = builder.build_extract_value(result, 1);
+  let all_ones = llvm::Value::const_all_ones(ty);
+  let saturated = match op {
+    "uadd" => all_ones,
+    "usub" => llvm::Value::const_null(ty),
+    // signed operations overflow in the direction of the sign of the lhs
+    _ => {
+      let max =
+        builder.build_lshr(all_ones, llvm::Value::const_int(ty, 1));
+      let min = builder.build_not(max);
+      let negative = builder.build_icmp(llvm::IntSLT, lllhs,
+        llvm::Value::const_null(ty));
+      builder.build_select(negative, min, max)
+    }
+  };
+  builder.build_select(overflowed, saturated, wrapped)
+}
+
+// byte swaps, then reverses the bits within each byte by swapping nibbles,
+// pairs of bits, and then bits. byte swapping needs a multiple of 16 bits, so
+// narrower or odd widths are reversed in a wider integer and shifted down
+fn build_bitreverse<'a>(builder: &llvm::Builder, inner: &Value<'a>,
+    values: &[llvm::Value]) -> llvm::Value {
+  let width = inner.ty().int_size();
+  let wide_width = (width + 15) / 16 * 16;
+  let wide_ty = llvm::get_int_type(wide_width);
+  let mut value = values[inner.number as usize];
+  if wide_width != width {
+    value = builder.build_zext(value, wide_ty);
+  }
+  value = builder.build_intrinsic_call(&format!("llvm.bswap.i{}", wide_width),
+    wide_ty, &[value]);
+  let words = ((wide_width + 63) / 64) as usize;
+  for &(shift, pattern) in &[(4, 0xF0u8), (2, 0xCC), (1, 0xAA)] {
+    let pattern = pattern as u64 * 0x0101010101010101;
+    let high = llvm::Value::const_int_words(wide_ty, &vec![pattern; words]);
+    let low = llvm::Value::const_int_words(wide_ty, &vec![!pattern; words]);
+    let shift = llvm::Value::const_int(wide_ty, shift);
+    value = builder.build_or(
+      builder.build_lshr(builder.build_and(value, high), shift),
+      builder.build_shl(builder.build_and(value, low), shift));
+  }
+  if wide_width != width {
+    value = builder.build_lshr(value,
+      llvm::Value::const_int(wide_ty, (wide_width - width) as u64));
+    value = builder.build_trunc(value, llvm::get_int_type(width));
+  }
+  value
+}
+
+// shifting by the full width is undefined, so a shift of zero modulo the
+// width is selected separately
+fn build_funnel_shift<'a>(builder: &llvm::Builder,
+    target_data: &llvm::TargetData, left: bool, hi: &Value<'a>,
+    lo: &Value<'a>, amount: &Value<'a>, values: &[llvm::Value])
+    -> llvm::Value {
+  let ty = llvm::get_type(target_data, hi.ty());
+  let llhi = values[hi.number as usize];
+  let lllo = values[lo.number as usize];
+  let width = llvm::Value::const_int(ty, hi.ty().int_size() as u64);
+  let shift = builder.build_urem(values[amount.number as usize], width);
+  let inverse = builder.build_sub(width, shift);
+  let (hi_shift, lo_shift) = if left {
+    (shift, inverse)
+  } else {
+    (inverse, shift)
   };
-  values.push(llval)
+  let shifted = builder.build_or(builder.build_shl(llhi, hi_shift),
+    builder.build_lshr(lllo, lo_shift));
+  let unshifted = if left {
+    llhi
+  } else {
+    lllo
+  };
+  let is_zero =
+    builder.build_icmp(llvm::IntEQ, shift, llvm::Value::const_null(ty));
+  builder.build_select(is_zero, unshifted, shifted)
 }
 
-fn build_terminator(term: Terminator, builder: &llvm::Builder,
+fn build_terminator<'a>(term: &Terminator<'a>, builder: &llvm::Builder,
+    functions: &HashMap<&Function<'a>, llvm::Value>,
     blocks: &[llvm::BasicBlock], values: &[llvm::Value]) {
-  match term {
-    Terminator::Branch(b) => {
+  match *term {
+    Terminator::Branch(b, _) => {
       builder.build_br(blocks[b.number as usize]);
     },
+    Terminator::CondBranch {
+      cond,
+      then,
+      else_,
+    } => {
+      builder.build_cond_br(values[cond.number as usize],
+        blocks[then.number as usize], blocks[else_.number as usize]);
+    },
+    Terminator::Switch {
+      value,
+      default,
+      ref cases,
+    } => {
+      let int_ty = value.ty().int_size();
+      let switch = builder.build_switch(values[value.number as usize],
+        blocks[default.number as usize], cases.len() as u32);
+      for &(n, blk) in cases.iter() {
+        llvm::add_case(switch,
+          llvm::Value::const_int(llvm::get_int_type(int_ty), n),
+          blocks[blk.number as usize]);
+      }
+    },
     Terminator::Return(r) => {
       builder.build_ret(values[r.number as usize]);
     }
+    Terminator::ReturnVoid => {
+      builder.build_void_ret();
+    }
+    Terminator::Unreachable => {
+      builder.build_unreachable();
+    }
+    Terminator::Invoke {
+      function,
+      ref parameters,
+      normal,
+      unwind,
+    } => {
+      let mut llvm_params = vec![];
+      for param in parameters.iter() {
+        llvm_params.push(values[param.number as usize]);
+      }
+      builder.build_invoke(
+        *functions.get(function).expect("pcb_ice: Blorghle"), &llvm_params,
+        blocks[normal.number as usize], blocks[unwind.number as usize],
+        function.ty.call_conv);
+    }
+    Terminator::Resume(exception) => {
+      builder.build_resume(values[exception.number as usize]);
+    }
     Terminator::None => {
       panic!("pcb_assert: no terminator set")
     }
@@ -3,6 +3,8 @@
 use std;
 use std::ffi::{CStr, CString};
 use core::ty;
+use core::function::{AtomicOrdering, AtomicRmwOp, Linkage, Visibility,
+  WrapFlags};
 
 extern crate llvm_sys;
 extern crate libc;
@@ -17,6 +19,50 @@ use self::llvm_sys::analysis::*;
 
 // TODO(ubsan): ZSTs should not be passed into functions
 
+pub enum PCBOpaqueDIBuilder {}
+pub type PCBDIBuilderRef = *mut PCBOpaqueDIBuilder;
+pub enum PCBOpaqueMetadata {}
+pub type PCBMetadataRef = *mut PCBOpaqueMetadata;
+
+// defined in shim.cpp
+extern {
+  fn PCBSetNoSignedWrap(value: LLVMValueRef);
+  fn PCBSetNoUnsignedWrap(value: LLVMValueRef);
+  fn PCBSetExact(value: LLVMValueRef);
+  fn PCBSetMustTail(call: LLVMValueRef);
+
+  fn PCBCreateDIBuilder(module: LLVMModuleRef, name: *const c_char,
+    name_len: usize, directory: *const c_char, directory_len: usize,
+    optimized: bool, file: *mut PCBMetadataRef) -> PCBDIBuilderRef;
+  fn PCBDisposeDIBuilder(builder: PCBDIBuilderRef);
+  fn PCBAddDebugInfoVersion(module: LLVMModuleRef);
+  fn PCBDICreateBasicType(builder: PCBDIBuilderRef, name: *const c_char,
+    name_len: usize, size_in_bits: u64, encoding: u32) -> PCBMetadataRef;
+  fn PCBDICreatePointerType(builder: PCBDIBuilderRef, size_in_bits: u64)
+    -> PCBMetadataRef;
+  fn PCBDICreateStructType(builder: PCBDIBuilderRef, file: PCBMetadataRef,
+    size_in_bits: u64, align_in_bits: u32, fields: *const PCBMetadataRef,
+    offsets_in_bits: *const u64, fields_len: usize) -> PCBMetadataRef;
+  fn PCBDICreateArrayType(builder: PCBDIBuilderRef, size_in_bits: u64,
+    align_in_bits: u32, element: PCBMetadataRef, len: u64) -> PCBMetadataRef;
+  fn PCBDICreateFunction(builder: PCBDIBuilderRef, function: LLVMValueRef,
+    file: PCBMetadataRef, name: *const c_char, name_len: usize, line: u32,
+    types: *const PCBMetadataRef, types_len: usize, optimized: bool)
+    -> PCBMetadataRef;
+  fn PCBDICreateParameter(builder: PCBDIBuilderRef,
+    subprogram: PCBMetadataRef, name: *const c_char, name_len: usize,
+    arg_no: u32, ty: PCBMetadataRef, value: LLVMValueRef,
+    block: LLVMBasicBlockRef);
+  fn PCBDICreateLexicalBlockFile(builder: PCBDIBuilderRef,
+    scope: PCBMetadataRef, file: PCBMetadataRef) -> PCBMetadataRef;
+  fn PCBSetCurrentDebugLocation(builder: LLVMBuilderRef, line: u32,
+    column: u32, scope: PCBMetadataRef);
+}
+
+pub const DW_ATE_BOOLEAN: u32 = 0x02;
+pub const DW_ATE_FLOAT: u32 = 0x04;
+pub const DW_ATE_SIGNED: u32 = 0x05;
+
 macro_rules! cstr {
   ($s:expr) => (
     concat!($s, "\0").as_ptr() as *const self::libc::c_char
@@ -28,6 +74,12 @@ pub use self::llvm_sys::LLVMIntPredicate::{LLVMIntEQ as IntEQ,
   LLVMIntULT as IntULT, LLVMIntULE as IntULE, LLVMIntSGT as IntSGT,
   LLVMIntSGE as IntSGE, LLVMIntSLT as IntSLT, LLVMIntSLE as IntSLE};
 
+pub use self::llvm_sys::LLVMRealPredicate::{LLVMRealOEQ as RealOEQ,
+  LLVMRealONE as RealONE, LLVMRealOLT as RealOLT, LLVMRealOGT as RealOGT,
+  LLVMRealOLE as RealOLE, LLVMRealOGE as RealOGE, LLVMRealUEQ as RealUEQ,
+  LLVMRealUNE as RealUNE, LLVMRealULT as RealULT, LLVMRealUGT as RealUGT,
+  LLVMRealULE as RealULE, LLVMRealUGE as RealUGE};
+
 pub use self::llvm_sys::target_machine::LLVMCodeGenOptLevel::{
   LLVMCodeGenLevelNone as NoOptimization,
   LLVMCodeGenLevelLess as LessOptimization,
@@ -45,6 +97,20 @@ impl Value {
     }
   }
 
+  // for integers wider than 64 bits; the words are least significant first
+  pub fn const_int_words(ty: Type, words: &[u64]) -> Value {
+    unsafe {
+      Value(LLVMConstIntOfArbitraryPrecision(ty.0, words.len() as u32,
+        words.as_ptr()))
+    }
+  }
+
+  pub fn const_float(ty: Type, value: f64) -> Value {
+    unsafe {
+      Value(LLVMConstReal(ty.0, value))
+    }
+  }
+
   pub fn const_bool(value: bool) -> Value {
     unsafe {
       Value(LLVMConstInt(LLVMInt1Type(),
@@ -61,6 +127,44 @@ impl Value {
     }
   }
 
+  pub fn const_all_ones(ty: Type) -> Value {
+    unsafe {
+      Value(LLVMConstAllOnes(ty.0))
+    }
+  }
+
+  pub fn const_null(ty: Type) -> Value {
+    unsafe {
+      Value(LLVMConstNull(ty.0))
+    }
+  }
+
+  // not null-terminated
+  pub fn const_bytes(bytes: &[u8]) -> Value {
+    unsafe {
+      Value(LLVMConstString(bytes.as_ptr() as *const c_char,
+        bytes.len() as u32, true as LLVMBool))
+    }
+  }
+
+  pub fn const_bitcast(value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMConstBitCast(value.0, ty.0))
+    }
+  }
+
+  pub fn null() -> Value {
+    Value(std::ptr::null_mut())
+  }
+
+  pub fn const_array(ty: Type, values: &[Value]) -> Value {
+    unsafe {
+      let llvm_values = Self::llvm_slice(values);
+      let len = llvm_values.len() as u32;
+      Value(LLVMConstArray(ty.0, llvm_values.as_ptr() as *mut _, len))
+    }
+  }
+
   pub fn get_param(func: Value, number: u32) -> Value {
     unsafe {
       Value(LLVMGetParam(func.0, number))
@@ -95,6 +199,14 @@ impl Builder {
     }
   }
 
+  // given to everything built from now on; `scope` is a subprogram or a
+  // lexical block file
+  pub fn set_debug_location(&self, line: u32, column: u32, scope: Metadata) {
+    unsafe {
+      PCBSetCurrentDebugLocation(self.0, line, column, scope.0);
+    }
+  }
+
   pub fn build_call(&self, callee: Value, args: &[Value]) -> Value {
     unsafe {
       let args = Value::llvm_slice(args);
@@ -104,6 +216,60 @@ impl Builder {
     }
   }
 
+  // calls the LLVM intrinsic `name`, declaring it in the current module if it
+  // hasn't been already; the argument types are taken from `args`
+  pub fn build_intrinsic_call(&self, name: &str, output: Type, args: &[Value])
+      -> Value {
+    unsafe {
+      let module = LLVMGetGlobalParent(
+        LLVMGetBasicBlockParent(LLVMGetInsertBlock(self.0)));
+      let name = CString::new(name.to_owned()).expect("intrinsic call: ");
+      let mut intrinsic = LLVMGetNamedFunction(module, name.as_ptr());
+      if intrinsic.is_null() {
+        let mut arg_tys =
+          args.iter().map(|a| LLVMTypeOf(a.0)).collect::<Vec<_>>();
+        let ty = LLVMFunctionType(output.0, arg_tys.as_mut_ptr(),
+          arg_tys.len() as u32, false as LLVMBool);
+        intrinsic = LLVMAddFunction(module, name.as_ptr(), ty);
+      }
+      self.build_call(Value(intrinsic), args)
+    }
+  }
+
+  // the function type of the assembly is taken from `output` and `args`
+  pub fn build_inline_asm(&self, template: &str, constraints: &str,
+      side_effects: bool, align_stack: bool, output: Type, args: &[Value])
+      -> Value {
+    unsafe {
+      let mut arg_tys =
+        args.iter().map(|a| LLVMTypeOf(a.0)).collect::<Vec<_>>();
+      let ty = LLVMFunctionType(output.0, arg_tys.as_mut_ptr(),
+        arg_tys.len() as u32, false as LLVMBool);
+      let template = CString::new(template.to_owned())
+        .expect("build inline asm: ");
+      let constraints = CString::new(constraints.to_owned())
+        .expect("build inline asm: ");
+      let asm = LLVMConstInlineAsm(ty, template.as_ptr(),
+        constraints.as_ptr(), side_effects as LLVMBool,
+        align_stack as LLVMBool);
+      self.build_call(Value(asm), args)
+    }
+  }
+
+  // tail calls are `musttail`, so they're guaranteed to reuse the caller's
+  // stack frame
+  pub fn build_call_with_conv(&self, callee: Value, args: &[Value],
+      call_conv: ty::CallConv, tail: bool) -> Value {
+    let call = self.build_call(callee, args);
+    unsafe {
+      LLVMSetInstructionCallConv(call.0, get_call_conv(call_conv));
+      if tail {
+        PCBSetMustTail(call.0);
+      }
+    }
+    call
+  }
+
   pub fn build_br(&self, block: BasicBlock) {
     unsafe {
       LLVMBuildBr(self.0, block.0);
@@ -117,6 +283,13 @@ impl Builder {
     }
   }
 
+  pub fn build_switch(&self, value: Value, default: BasicBlock,
+      num_cases: u32) -> Value {
+    unsafe {
+      Value(LLVMBuildSwitch(self.0, value.0, default.0, num_cases))
+    }
+  }
+
   pub fn build_ret(&self, ret: Value) {
     unsafe {
       LLVMBuildRet(self.0, ret.0);
@@ -129,6 +302,49 @@ impl Builder {
     }
   }
 
+  pub fn build_phi(&self, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildPhi(self.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_unreachable(&self) {
+    unsafe {
+      LLVMBuildUnreachable(self.0);
+    }
+  }
+
+  pub fn build_invoke(&self, callee: Value, args: &[Value], then: BasicBlock,
+      catch: BasicBlock, call_conv: ty::CallConv) -> Value {
+    unsafe {
+      let args = Value::llvm_slice(args);
+      let len = args.len() as u32;
+      let invoke = LLVMBuildInvoke(self.0, callee.0, args.as_ptr() as *mut _,
+        len, then.0, catch.0, cstr!(""));
+      LLVMSetInstructionCallConv(invoke, get_call_conv(call_conv));
+      Value(invoke)
+    }
+  }
+
+  pub fn build_landing_pad(&self, ty: Type, personality: Value, cleanup: bool,
+      clauses: &[Value]) -> Value {
+    unsafe {
+      let landing_pad = LLVMBuildLandingPad(self.0, ty.0, personality.0,
+        clauses.len() as u32, cstr!(""));
+      for clause in clauses {
+        LLVMAddClause(landing_pad, clause.0);
+      }
+      LLVMSetCleanup(landing_pad, cleanup as LLVMBool);
+      Value(landing_pad)
+    }
+  }
+
+  pub fn build_resume(&self, exception: Value) {
+    unsafe {
+      LLVMBuildResume(self.0, exception.0);
+    }
+  }
+
   pub fn build_alloca(&self, ty: Type, name: &str) -> Value {
     unsafe {
       Value(LLVMBuildAlloca(self.0, ty.0,
@@ -136,6 +352,26 @@ impl Builder {
     }
   }
 
+  // builds the alloca at the start of the current function's entry block,
+  // so that it's only allocated once, and can be promoted to a register
+  pub fn build_entry_alloca(&self, ty: Type, name: &str) -> Value {
+    unsafe {
+      let entry = LLVMGetEntryBasicBlock(
+        LLVMGetBasicBlockParent(LLVMGetInsertBlock(self.0)));
+      let builder = LLVMCreateBuilder();
+      let first = LLVMGetFirstInstruction(entry);
+      if first.is_null() {
+        LLVMPositionBuilderAtEnd(builder, entry);
+      } else {
+        LLVMPositionBuilderBefore(builder, first);
+      }
+      let ret = Value(LLVMBuildAlloca(builder, ty.0,
+        CString::new(name.to_owned()).expect("build alloca: ").as_ptr()));
+      LLVMDisposeBuilder(builder);
+      ret
+    }
+  }
+
   pub fn build_load(&self, ptr: Value) -> Value {
     unsafe {
       Value(LLVMBuildLoad(self.0, ptr.0, cstr!("")))
@@ -148,6 +384,104 @@ impl Builder {
     }
   }
 
+  // atomic loads and stores need an explicit alignment
+  pub fn build_atomic_load(&self, ptr: Value, ordering: AtomicOrdering,
+      align: u32) -> Value {
+    unsafe {
+      let load = LLVMBuildLoad(self.0, ptr.0, cstr!(""));
+      LLVMSetOrdering(load, get_atomic_ordering(ordering));
+      LLVMSetAlignment(load, align);
+      Value(load)
+    }
+  }
+
+  pub fn build_atomic_store(&self, dst: Value, src: Value,
+      ordering: AtomicOrdering, align: u32) {
+    unsafe {
+      let store = LLVMBuildStore(self.0, src.0, dst.0);
+      LLVMSetOrdering(store, get_atomic_ordering(ordering));
+      LLVMSetAlignment(store, align);
+    }
+  }
+
+  pub fn build_atomic_rmw(&self, op: AtomicRmwOp, ptr: Value, value: Value,
+      ordering: AtomicOrdering) -> Value {
+    use self::llvm_sys::LLVMAtomicRMWBinOp::*;
+    let op = match op {
+      AtomicRmwOp::Xchg => LLVMAtomicRMWBinOpXchg,
+      AtomicRmwOp::Add => LLVMAtomicRMWBinOpAdd,
+      AtomicRmwOp::Sub => LLVMAtomicRMWBinOpSub,
+      AtomicRmwOp::And => LLVMAtomicRMWBinOpAnd,
+      AtomicRmwOp::Or => LLVMAtomicRMWBinOpOr,
+      AtomicRmwOp::Xor => LLVMAtomicRMWBinOpXor,
+      AtomicRmwOp::SMax => LLVMAtomicRMWBinOpMax,
+      AtomicRmwOp::SMin => LLVMAtomicRMWBinOpMin,
+      AtomicRmwOp::UMax => LLVMAtomicRMWBinOpUMax,
+      AtomicRmwOp::UMin => LLVMAtomicRMWBinOpUMin,
+    };
+    unsafe {
+      Value(LLVMBuildAtomicRMW(self.0, op, ptr.0, value.0,
+        get_atomic_ordering(ordering), false as LLVMBool))
+    }
+  }
+
+  pub fn build_cmpxchg(&self, ptr: Value, expected: Value, new: Value,
+      success: AtomicOrdering, failure: AtomicOrdering) -> Value {
+    unsafe {
+      Value(LLVMBuildAtomicCmpXchg(self.0, ptr.0, expected.0, new.0,
+        get_atomic_ordering(success), get_atomic_ordering(failure),
+        false as LLVMBool))
+    }
+  }
+
+  pub fn build_fence(&self, ordering: AtomicOrdering) {
+    unsafe {
+      LLVMBuildFence(self.0, get_atomic_ordering(ordering), false as LLVMBool,
+        cstr!(""));
+    }
+  }
+
+  pub fn build_va_arg(&self, list: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildVAArg(self.0, list.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_bitcast(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildBitCast(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_extract_value(&self, aggregate: Value, index: u32) -> Value {
+    unsafe {
+      Value(LLVMBuildExtractValue(self.0, aggregate.0, index, cstr!("")))
+    }
+  }
+
+  pub fn build_insert_value(&self, aggregate: Value, index: u32,
+      element: Value) -> Value {
+    unsafe {
+      Value(LLVMBuildInsertValue(self.0, aggregate.0, element.0, index,
+        cstr!("")))
+    }
+  }
+
+  pub fn build_struct_gep(&self, ptr: Value, index: u32) -> Value {
+    unsafe {
+      Value(LLVMBuildStructGEP(self.0, ptr.0, index, cstr!("")))
+    }
+  }
+
+  pub fn build_gep(&self, ptr: Value, indices: &[Value]) -> Value {
+    unsafe {
+      let indices = Value::llvm_slice(indices);
+      let len = indices.len() as u32;
+      Value(LLVMBuildGEP(self.0, ptr.0, indices.as_ptr() as *mut _, len,
+        cstr!("")))
+    }
+  }
+
   pub fn build_neg(&self, inner: Value) -> Value {
     unsafe {
       Value(LLVMBuildNeg(self.0, inner.0, cstr!("")))
@@ -228,12 +562,94 @@ impl Builder {
     }
   }
 
+  pub fn build_fadd(&self, lhs: Value, rhs: Value) -> Value {
+    unsafe {
+      Value(LLVMBuildFAdd(self.0, lhs.0, rhs.0, cstr!("")))
+    }
+  }
+  pub fn build_fsub(&self, lhs: Value, rhs: Value) -> Value {
+    unsafe {
+      Value(LLVMBuildFSub(self.0, lhs.0, rhs.0, cstr!("")))
+    }
+  }
+  pub fn build_fmul(&self, lhs: Value, rhs: Value) -> Value {
+    unsafe {
+      Value(LLVMBuildFMul(self.0, lhs.0, rhs.0, cstr!("")))
+    }
+  }
+  pub fn build_fdiv(&self, lhs: Value, rhs: Value) -> Value {
+    unsafe {
+      Value(LLVMBuildFDiv(self.0, lhs.0, rhs.0, cstr!("")))
+    }
+  }
+  pub fn build_frem(&self, lhs: Value, rhs: Value) -> Value {
+    unsafe {
+      Value(LLVMBuildFRem(self.0, lhs.0, rhs.0, cstr!("")))
+    }
+  }
+
+  pub fn build_select(&self, cond: Value, then: Value, else_: Value)
+      -> Value {
+    unsafe {
+      Value(LLVMBuildSelect(self.0, cond.0, then.0, else_.0, cstr!("")))
+    }
+  }
+
   pub fn build_icmp(&self, pred: LLVMIntPredicate, lhs: Value, rhs: Value)
       -> Value {
     unsafe {
       Value(LLVMBuildICmp(self.0, pred, lhs.0, rhs.0, cstr!("")))
     }
   }
+
+  pub fn build_fcmp(&self, pred: LLVMRealPredicate, lhs: Value, rhs: Value)
+      -> Value {
+    unsafe {
+      Value(LLVMBuildFCmp(self.0, pred, lhs.0, rhs.0, cstr!("")))
+    }
+  }
+
+  pub fn build_fp_to_ui(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildFPToUI(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_fp_to_si(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildFPToSI(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_ui_to_fp(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildUIToFP(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_si_to_fp(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildSIToFP(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_trunc(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildTrunc(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_zext(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildZExt(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
+
+  pub fn build_sext(&self, value: Value, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMBuildSExt(self.0, value.0, ty.0, cstr!("")))
+    }
+  }
 }
 
 impl std::ops::Drop for Builder {
@@ -247,15 +663,22 @@ impl std::ops::Drop for Builder {
 #[derive(Copy, Clone, Debug)]
 pub struct BasicBlock(LLVMBasicBlockRef);
 impl BasicBlock {
-  pub fn append(func: Value, num: u32) -> Self {
+  pub fn append(func: Value, name: &str) -> Self {
     unsafe {
       BasicBlock(LLVMAppendBasicBlock(func.0,
-        CString::new(format!("bb{}", num)).expect("BasicBlock::append: ")
+        CString::new(name.to_owned()).expect("BasicBlock::append: ")
           .as_ptr()))
     }
   }
+
+  pub fn get_terminator(self) -> Value {
+    unsafe {
+      Value(LLVMGetBasicBlockTerminator(self.0))
+    }
+  }
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct Type(LLVMTypeRef);
 
 #[derive(Copy, Clone, Debug)]
@@ -329,6 +752,137 @@ impl TargetData {
       TargetData(LLVMGetTargetMachineData(machine.0))
     }
   }
+
+  pub fn size_in_bits(&self, ty: Type) -> u64 {
+    unsafe {
+      LLVMABISizeOfType(self.0, ty.0) * 8
+    }
+  }
+
+  pub fn store_size_in_bits(&self, ty: Type) -> u64 {
+    unsafe {
+      LLVMStoreSizeOfType(self.0, ty.0) * 8
+    }
+  }
+
+  pub fn align_in_bits(&self, ty: Type) -> u32 {
+    unsafe {
+      LLVMABIAlignmentOfType(self.0, ty.0) * 8
+    }
+  }
+
+  pub fn offset_of_field_in_bits(&self, ty: Type, field: u32) -> u64 {
+    unsafe {
+      LLVMOffsetOfElement(self.0, ty.0, field) * 8
+    }
+  }
+
+  pub fn pointer_size_in_bits(&self) -> u64 {
+    unsafe {
+      LLVMPointerSize(self.0) as u64 * 8
+    }
+  }
+}
+
+// a node of debug info; may be null
+#[derive(Copy, Clone, Debug)]
+pub struct Metadata(PCBMetadataRef);
+impl Metadata {
+  pub fn null() -> Self {
+    Metadata(std::ptr::null_mut())
+  }
+}
+
+// builds the compile unit of a single source file
+#[derive(Debug)]
+pub struct DIBuilder(PCBDIBuilderRef, Metadata);
+impl DIBuilder {
+  pub fn new(module: &Module, name: &str, directory: &str, optimized: bool)
+      -> Self {
+    unsafe {
+      let mut file = std::ptr::null_mut();
+      let builder = PCBCreateDIBuilder(module.0,
+        name.as_ptr() as *const c_char, name.len(),
+        directory.as_ptr() as *const c_char, directory.len(), optimized,
+        &mut file);
+      DIBuilder(builder, Metadata(file))
+    }
+  }
+
+  pub fn file(&self) -> Metadata {
+    self.1
+  }
+
+  pub fn basic_type(&self, name: &str, size_in_bits: u64, encoding: u32)
+      -> Metadata {
+    unsafe {
+      Metadata(PCBDICreateBasicType(self.0, name.as_ptr() as *const c_char,
+        name.len(), size_in_bits, encoding))
+    }
+  }
+
+  pub fn pointer_type(&self, size_in_bits: u64) -> Metadata {
+    unsafe {
+      Metadata(PCBDICreatePointerType(self.0, size_in_bits))
+    }
+  }
+
+  pub fn struct_type(&self, size_in_bits: u64, align_in_bits: u32,
+      fields: &[Metadata], offsets_in_bits: &[u64]) -> Metadata {
+    assert!(fields.len() == offsets_in_bits.len(),
+      "pcb_ice: struct_type: mismatched fields and offsets");
+    unsafe {
+      Metadata(PCBDICreateStructType(self.0, (self.1).0, size_in_bits,
+        align_in_bits, fields.as_ptr() as *const PCBMetadataRef,
+        offsets_in_bits.as_ptr(), fields.len()))
+    }
+  }
+
+  pub fn array_type(&self, size_in_bits: u64, align_in_bits: u32,
+      element: Metadata, len: u64) -> Metadata {
+    unsafe {
+      Metadata(PCBDICreateArrayType(self.0, size_in_bits, align_in_bits,
+        element.0, len))
+    }
+  }
+
+  // `types` is the output type, null if it's void, followed by the input
+  // types; the returned subprogram is attached to `func`
+  pub fn function(&self, func: Value, name: &str, line: u32,
+      types: &[Metadata], optimized: bool) -> Metadata {
+    unsafe {
+      Metadata(PCBDICreateFunction(self.0, func.0, (self.1).0,
+        name.as_ptr() as *const c_char, name.len(), line,
+        types.as_ptr() as *const PCBMetadataRef, types.len(), optimized))
+    }
+  }
+
+  // `arg_no` starts at 1; the location of `value` is recorded at the end of
+  // `block`
+  pub fn parameter(&self, subprogram: Metadata, name: &str, arg_no: u32,
+      ty: Metadata, value: Value, block: BasicBlock) {
+    unsafe {
+      PCBDICreateParameter(self.0, subprogram.0,
+        name.as_ptr() as *const c_char, name.len(), arg_no, ty.0, value.0,
+        block.0);
+    }
+  }
+
+  pub fn lexical_block_file(&self, scope: Metadata, file: Metadata)
+      -> Metadata {
+    unsafe {
+      Metadata(PCBDICreateLexicalBlockFile(self.0, scope.0, file.0))
+    }
+  }
+}
+
+impl std::ops::Drop for DIBuilder {
+  // finalizes the compile unit
+  fn drop(&mut self) {
+    unsafe {
+      PCBDisposeDIBuilder(self.0);
+    }
+  }
 }
 
 pub struct Module(LLVMModuleRef);
@@ -346,12 +900,25 @@ impl Module {
     }
   }
 
+  pub fn add_global(&self, name: &str, ty: Type) -> Value {
+    unsafe {
+      Value(LLVMAddGlobal(self.0, ty.0,
+        CString::new(name.to_owned()).expect("add_global: ").as_ptr()))
+    }
+  }
+
   pub fn dump(&self) {
     unsafe {
       LLVMDumpModule(self.0)
     }
   }
 
+  pub fn add_debug_info_version(&self) {
+    unsafe {
+      PCBAddDebugInfoVersion(self.0);
+    }
+  }
+
   pub fn verify(&self) {
     unsafe {
       let mut error: *mut c_char = std::mem::uninitialized();
@@ -412,28 +979,183 @@ pub fn size_of_type(target_data: &TargetData, ty: &ty::Type) -> u64 {
   }
 }
 
+pub fn add_case(switch: Value, on: Value, dest: BasicBlock) {
+  unsafe {
+    LLVMAddCase(switch.0, on.0, dest.0);
+  }
+}
+
+// only non-void instructions and arguments which haven't been named are
+// named; a pcb value may be lowered to a constant, a function or a global, or
+// to a value built for another pcb value, and those mustn't be renamed. LLVM
+// doesn't allow naming void values at all
+pub fn set_value_name(value: Value, name: &str) {
+  use self::llvm_sys::LLVMTypeKind::LLVMVoidTypeKind;
+  unsafe {
+    if LLVMIsAInstruction(value.0).is_null()
+        && LLVMIsAArgument(value.0).is_null() {
+      return;
+    }
+    if LLVMGetTypeKind(LLVMTypeOf(value.0)) == LLVMVoidTypeKind {
+      return;
+    }
+    if !CStr::from_ptr(LLVMGetValueName(value.0)).to_bytes().is_empty() {
+      return;
+    }
+    LLVMSetValueName(value.0,
+      CString::new(name.to_owned()).expect("set_value_name: ").as_ptr());
+  }
+}
+
+// the C API can only build instructions with one of nsw and nuw, and can't
+// build exact udivs or shifts, so the flags are set on the built instruction
+pub fn set_wrap_flags(value: Value, flags: WrapFlags) -> Value {
+  unsafe {
+    if flags.nsw {
+      PCBSetNoSignedWrap(value.0);
+    }
+    if flags.nuw {
+      PCBSetNoUnsignedWrap(value.0);
+    }
+  }
+  value
+}
+
+pub fn set_exact(value: Value, exact: bool) -> Value {
+  if exact {
+    unsafe {
+      PCBSetExact(value.0);
+    }
+  }
+  value
+}
+
+pub fn add_incoming(phi: Value, value: Value, block: BasicBlock) {
+  unsafe {
+    let mut value = value.0;
+    let mut block = block.0;
+    LLVMAddIncoming(phi.0, &mut value, &mut block, 1);
+  }
+}
+
+pub fn set_initializer(global: Value, init: Value) {
+  unsafe {
+    LLVMSetInitializer(global.0, init.0);
+  }
+}
+
+pub fn set_global_constant(global: Value, constant: bool) {
+  unsafe {
+    LLVMSetGlobalConstant(global.0, constant as LLVMBool);
+  }
+}
+
+// weak declarations are references to symbols that may not be defined
+pub fn set_linkage(global: Value, linkage: Linkage, declaration: bool) {
+  use self::llvm_sys::LLVMLinkage::*;
+  let linkage = match linkage {
+    Linkage::External => LLVMExternalLinkage,
+    Linkage::Internal => LLVMInternalLinkage,
+    Linkage::Private => LLVMPrivateLinkage,
+    Linkage::Weak if declaration => LLVMExternalWeakLinkage,
+    Linkage::Weak => LLVMWeakAnyLinkage,
+    Linkage::LinkOnceOdr => LLVMLinkOnceODRLinkage,
+    Linkage::Common => LLVMCommonLinkage,
+  };
+  unsafe {
+    LLVMSetLinkage(global.0, linkage);
+  }
+}
+
+fn get_atomic_ordering(ordering: AtomicOrdering) -> LLVMAtomicOrdering {
+  use self::llvm_sys::LLVMAtomicOrdering::*;
+  match ordering {
+    AtomicOrdering::Relaxed => LLVMAtomicOrderingMonotonic,
+    AtomicOrdering::Acquire => LLVMAtomicOrderingAcquire,
+    AtomicOrdering::Release => LLVMAtomicOrderingRelease,
+    AtomicOrdering::AcqRel => LLVMAtomicOrderingAcquireRelease,
+    AtomicOrdering::SeqCst => LLVMAtomicOrderingSequentiallyConsistent,
+  }
+}
+
+pub fn abi_align_of_type(target_data: &TargetData, ty: &ty::Type) -> u32 {
+  unsafe {
+    LLVMABIAlignmentOfType(target_data.0, get_type(target_data, ty).0)
+  }
+}
+
+pub fn set_personality(func: Value, personality: Value) {
+  unsafe {
+    LLVMSetPersonalityFn(func.0, personality.0);
+  }
+}
+
+pub fn set_function_call_conv(func: Value, call_conv: ty::CallConv) {
+  unsafe {
+    LLVMSetFunctionCallConv(func.0, get_call_conv(call_conv));
+  }
+}
+
+fn get_call_conv(call_conv: ty::CallConv) -> u32 {
+  use self::llvm_sys::LLVMCallConv::*;
+  (match call_conv {
+    ty::CallConv::C => LLVMCCallConv,
+    ty::CallConv::Fast => LLVMFastCallConv,
+    ty::CallConv::Cold => LLVMColdCallConv,
+  }) as u32
+}
+
+pub fn set_visibility(global: Value, visibility: Visibility) {
+  use self::llvm_sys::LLVMVisibility::*;
+  let visibility = match visibility {
+    Visibility::Default => LLVMDefaultVisibility,
+    Visibility::Hidden => LLVMHiddenVisibility,
+    Visibility::Protected => LLVMProtectedVisibility,
+  };
+  unsafe {
+    LLVMSetVisibility(global.0, visibility);
+  }
+}
+
 pub fn get_int_type(size: u32) -> Type {
   unsafe {
     Type(LLVMIntType(size))
   }
 }
 
-pub fn get_type(_target_data: &TargetData, ty: &ty::Type) -> Type {
+pub fn get_pointer_type(pointee: Type) -> Type {
+  unsafe {
+    Type(LLVMPointerType(pointee.0, 0))
+  }
+}
+
+pub fn get_type(target_data: &TargetData, ty: &ty::Type) -> Type {
   use core::ty::Type;
   unsafe {
     Type(match *ty {
       Type::Integer(size) => LLVMIntType(size),
-      /*
-      TypeVariant::Bool => LLVMInt1Type(),
-      TypeVariant::Pointer => LLVMPointerType(LLVMVoidType(), 0),
-      TypeVariant::Aggregate(ref v) => {
+      Type::Float(32) => LLVMFloatType(),
+      Type::Float(64) => LLVMDoubleType(),
+      Type::Float(size) => panic!("pcb_ice: invalid float size: {}", size),
+      Type::Bool => LLVMInt1Type(),
+      Type::Void => LLVMVoidType(),
+      Type::Pointer => LLVMPointerType(LLVMInt8Type(), 0),
+      Type::FnPtr(ref inputs, ref output, variadic, _) => {
+        let mut args = inputs.iter().map(|a| get_type(target_data, a).0)
+          .collect::<Vec<_>>();
+        LLVMPointerType(LLVMFunctionType(get_type(target_data, output).0,
+          args.as_mut_ptr(), args.len() as u32, variadic as LLVMBool), 0)
+      }
+      Type::Aggregate(ref v) => {
         let mut llvm =
-          v.iter().map(|el| get_type(target_data, *el).0)
+          v.iter().map(|el| get_type(target_data, el).0)
             .collect::<Vec<_>>();
         LLVMStructType(llvm.as_mut_ptr(), llvm.len() as u32,
           false as LLVMBool)
       }
-      */
+      Type::Array(ref elem, len) => {
+        LLVMArrayType(get_type(target_data, elem).0, len as u32)
+      }
     })
   }
 }
@@ -444,6 +1166,6 @@ pub fn get_function_type(target_data: &TargetData, ty: &ty::Function)
     let mut args = ty.inputs.iter().map(|a| get_type(target_data, *a).0)
       .collect::<Vec<_>>();
     Type(LLVMFunctionType(get_type(target_data, ty.output).0,
-      args.as_mut_ptr(), args.len() as u32, false as LLVMBool))
+      args.as_mut_ptr(), args.len() as u32, ty.variadic as LLVMBool))
   }
 }
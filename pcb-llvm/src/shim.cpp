@@ -0,0 +1,157 @@
+// the parts of the LLVM C++ API that the C API doesn't expose
+
+#include "llvm-c/Core.h"
+#include "llvm/IR/DIBuilder.h"
+#include "llvm/IR/DebugInfoMetadata.h"
+#include "llvm/IR/IRBuilder.h"
+#include "llvm/IR/Instruction.h"
+#include "llvm/IR/Instructions.h"
+#include "llvm/IR/Module.h"
+#include "llvm/IR/Operator.h"
+
+using namespace llvm;
+
+// operations on constants are folded by the builder, so `value` may not be an
+// instruction; the flags are dropped in that case, as there's nothing to
+// optimize
+
+extern "C" void PCBSetNoSignedWrap(LLVMValueRef value) {
+  Instruction *inst = dyn_cast<Instruction>(unwrap(value));
+  if (inst && isa<OverflowingBinaryOperator>(inst)) {
+    inst->setHasNoSignedWrap(true);
+  }
+}
+
+extern "C" void PCBSetNoUnsignedWrap(LLVMValueRef value) {
+  Instruction *inst = dyn_cast<Instruction>(unwrap(value));
+  if (inst && isa<OverflowingBinaryOperator>(inst)) {
+    inst->setHasNoUnsignedWrap(true);
+  }
+}
+
+extern "C" void PCBSetExact(LLVMValueRef value) {
+  Instruction *inst = dyn_cast<Instruction>(unwrap(value));
+  if (inst && isa<PossiblyExactOperator>(inst)) {
+    inst->setIsExact(true);
+  }
+}
+
+// the C API can only set the `tail` hint, which doesn't guarantee that the
+// caller's stack frame is reused
+extern "C" void PCBSetMustTail(LLVMValueRef call) {
+  cast<CallInst>(unwrap(call))->setTailCallKind(CallInst::TCK_MustTail);
+}
+
+// -- debug info --
+// the C API that llvm-sys binds has no DIBuilder. every DIBuilder builds a
+// single compile unit, so there's one for each source file
+
+extern "C" DIBuilder *PCBCreateDIBuilder(LLVMModuleRef module,
+    const char *name, size_t name_len, const char *directory,
+    size_t directory_len, bool optimized, DIFile **file) {
+  DIBuilder *builder = new DIBuilder(*unwrap(module));
+  *file = builder->createFile(StringRef(name, name_len),
+    StringRef(directory, directory_len));
+  builder->createCompileUnit(dwarf::DW_LANG_C99, *file, "pcb", optimized, "",
+    0);
+  return builder;
+}
+
+extern "C" void PCBDisposeDIBuilder(DIBuilder *builder) {
+  builder->finalize();
+  delete builder;
+}
+
+extern "C" void PCBAddDebugInfoVersion(LLVMModuleRef module) {
+  unwrap(module)->addModuleFlag(Module::Warning, "Debug Info Version",
+    DEBUG_METADATA_VERSION);
+  unwrap(module)->addModuleFlag(Module::Warning, "Dwarf Version", 4);
+}
+
+extern "C" DIType *PCBDICreateBasicType(DIBuilder *builder, const char *name,
+    size_t name_len, uint64_t size_in_bits, unsigned encoding) {
+  return builder->createBasicType(StringRef(name, name_len), size_in_bits,
+    encoding);
+}
+
+// pcb pointers are untyped, so they're described as void pointers
+extern "C" DIType *PCBDICreatePointerType(DIBuilder *builder,
+    uint64_t size_in_bits) {
+  return builder->createPointerType(nullptr, size_in_bits);
+}
+
+// the fields are named by their index
+extern "C" DIType *PCBDICreateStructType(DIBuilder *builder, DIFile *file,
+    uint64_t size_in_bits, uint32_t align_in_bits, DIType **fields,
+    const uint64_t *offsets_in_bits, size_t fields_len) {
+  DICompositeType *ty = builder->createStructType(file, "", file, 0,
+    size_in_bits, align_in_bits, DINode::FlagZero, nullptr, DINodeArray());
+  SmallVector<Metadata *, 8> members;
+  for (size_t i = 0; i < fields_len; ++i) {
+    members.push_back(builder->createMemberType(ty, std::to_string(i), file,
+      0, fields[i]->getSizeInBits(), 0, offsets_in_bits[i], DINode::FlagZero,
+      fields[i]));
+  }
+  builder->replaceArrays(ty, builder->getOrCreateArray(members));
+  return ty;
+}
+
+extern "C" DIType *PCBDICreateArrayType(DIBuilder *builder,
+    uint64_t size_in_bits, uint32_t align_in_bits, DIType *element,
+    uint64_t len) {
+  Metadata *subrange = builder->getOrCreateSubrange(0, len);
+  return builder->createArrayType(size_in_bits, align_in_bits, element,
+    builder->getOrCreateArray(subrange));
+}
+
+// `types` is the output type, which is null for void, followed by the input
+// types. the subprogram is attached to `function`
+extern "C" DISubprogram *PCBDICreateFunction(DIBuilder *builder,
+    LLVMValueRef function, DIFile *file, const char *name, size_t name_len,
+    unsigned line, DIType **types, size_t types_len, bool optimized) {
+  Function *func = unwrap<Function>(function);
+  SmallVector<Metadata *, 8> elements(types, types + types_len);
+  DISubroutineType *ty =
+    builder->createSubroutineType(builder->getOrCreateTypeArray(elements));
+  DISubprogram::DISPFlags flags = DISubprogram::SPFlagDefinition;
+  if (optimized) {
+    flags |= DISubprogram::SPFlagOptimized;
+  }
+  DISubprogram *subprogram = builder->createFunction(file,
+    StringRef(name, name_len), func->getName(), file, line, ty, line,
+    DINode::FlagPrototyped, flags);
+  func->setSubprogram(subprogram);
+  return subprogram;
+}
+
+// `arg_no` starts at 1; the dbg.value is added to the end of `block`
+extern "C" void PCBDICreateParameter(DIBuilder *builder,
+    DISubprogram *subprogram, const char *name, size_t name_len,
+    unsigned arg_no, DIType *ty, LLVMValueRef value,
+    LLVMBasicBlockRef block) {
+  DILocalVariable *var = builder->createParameterVariable(subprogram,
+    StringRef(name, name_len), arg_no, subprogram->getFile(),
+    subprogram->getLine(), ty, true);
+  DILocation *loc = DILocation::get(subprogram->getContext(),
+    subprogram->getLine(), 0, subprogram);
+  builder->insertDbgValueIntrinsic(unwrap(value), var,
+    builder->createExpression(), loc, unwrap(block));
+}
+
+// for locations in a different file from their function's
+extern "C" DIScope *PCBDICreateLexicalBlockFile(DIBuilder *builder,
+    DIScope *scope, DIFile *file) {
+  return builder->createLexicalBlockFile(scope, file);
+}
+
+// everything built from now on is given the location, until it's set again;
+// a null `scope` clears it
+extern "C" void PCBSetCurrentDebugLocation(LLVMBuilderRef builder,
+    unsigned line, unsigned column, DIScope *scope) {
+  if (scope) {
+    unwrap(builder)->SetCurrentDebugLocation(
+      DILocation::get(scope->getContext(), line, column, scope));
+  } else {
+    unwrap(builder)->SetCurrentDebugLocation(DebugLoc());
+  }
+}
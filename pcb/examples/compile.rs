@@ -27,7 +27,7 @@ fn main() {
                                           // block
     let main_end = Block::append(main);
     let main_ret = main_start.build_call(foo, &[]);
-    main_start.build_branch(main_end);
+    main_start.build_branch(main_end, &[]);
     main_end.build_call(foo, &[]); // useless, but you can still do
     main_end.build_return(main_ret);
   }
@@ -1,5 +1,8 @@
 extern crate pcb_core as core;
 
+pub use core::function::{AtomicOrdering, AtomicRmwOp, Linkage, Visibility,
+  WrapFlags};
+
 pub struct Ctxt(core::pcb::Ctxt);
 
 impl std::fmt::Display for Ctxt {
@@ -19,6 +22,21 @@ impl Ctxt {
       where B: core::backend::Backend, W: std::io::Write {
     B::build_and_write(self.0, output_file, print_extra_info)
   }
+
+  // whether the backend emits DWARF from source locations and function debug
+  // info; off by default
+  pub fn set_debug_info(&mut self, enabled: bool) {
+    self.0.debug_info = enabled;
+  }
+}
+
+#[derive(Copy, Clone)]
+pub struct SourceFile<'c>(&'c core::debug::SourceFile);
+
+impl<'c> SourceFile<'c> {
+  pub fn new(ctxt: &'c Ctxt, name: &str, directory: &str) -> Self {
+    SourceFile(ctxt.0.add_file(name, directory))
+  }
 }
 
 #[derive(Copy, Clone)]
@@ -26,7 +44,12 @@ pub struct Function<'c>(&'c core::function::Function<'c>);
 
 impl<'c> Function<'c> {
   pub fn new(ctxt: &'c Ctxt, name: &str, ty: ty::Function<'c>) -> Self {
-    Function(ctxt.0.add_function(name, ty.inner()))
+    Function(ctxt.0.add_function(name, ty.inner(), false))
+  }
+
+  // an external function, defined elsewhere; blocks may not be added to it
+  pub fn declare(ctxt: &'c Ctxt, name: &str, ty: ty::Function<'c>) -> Self {
+    Function(ctxt.0.add_function(name, ty.inner(), true))
   }
 
   pub fn get_argument(&self, number: u32) -> Value<'c> {
@@ -34,6 +57,190 @@ impl<'c> Function<'c> {
         get nonexistent argument");
     Value(self.0.values.get(number as usize).unwrap())
   }
+
+  pub fn set_linkage(self, linkage: Linkage) {
+    assert!(linkage != Linkage::Common, "pcb_assert: functions may not have \
+      common linkage");
+    if self.0.declaration {
+      assert!(linkage == Linkage::External || linkage == Linkage::Weak,
+        "pcb_assert: declarations may only have external or weak linkage");
+    }
+    chk_symbol_attributes(linkage, self.0.visibility.get());
+    self.0.linkage.set(linkage);
+  }
+
+  pub fn set_visibility(self, visibility: Visibility) {
+    chk_symbol_attributes(self.0.linkage.get(), visibility);
+    self.0.visibility.set(visibility);
+  }
+
+  // the personality routine, like C++'s __gxx_personality_v0; must be set
+  // before any landing pads are added, and can't be changed afterwards
+  pub fn set_personality(self, personality: Function<'c>) {
+    assert!(!self.0.declaration, "pcb_assert: attempt to set the \
+      personality of the declaration {}", self.0.name);
+    assert!(self.0.personality.get() == Some(personality.0)
+      || !self.0.blocks.iter().any(|blk| blk.landing_pad), "pcb_assert: \
+      attempt to change the personality of {} after adding landing pads",
+      self.0.name);
+    self.0.personality.set(Some(personality.0));
+  }
+
+  // `name` is the name shown by a debugger, and `param_names` are the names
+  // of the function's parameters
+  pub fn set_debug_info(self, name: &str, file: SourceFile<'c>, line: u32,
+      param_names: &[&str]) {
+    assert!(!self.0.declaration, "pcb_assert: attempt to set the debug info \
+      of the declaration {}", self.0.name);
+    assert!(param_names.len() == self.0.ty.inputs.len(), "pcb_assert: \
+      attempt to set debug info with the incorrect number of parameter names");
+    let mut inner_names = vec![];
+    for name in param_names {
+      inner_names.push((*name).to_owned());
+    }
+    *self.0.debug_info.borrow_mut() = Some(core::debug::FunctionDebugInfo {
+      name: name.to_owned(),
+      file: file.0,
+      line: line,
+      param_names: inner_names.into_boxed_slice(),
+    });
+  }
+}
+
+#[derive(Copy, Clone)]
+pub struct Global<'c>(&'c core::global::Global<'c>);
+
+impl<'c> Global<'c> {
+  // zero-initialized if there's no initializer
+  pub fn new(ctxt: &'c Ctxt, name: &str, ty: ty::Type<'c>,
+      init: Option<Constant<'c>>) -> Self {
+    assert!(*ty.inner() != core::ty::Type::Void, "pcb_assert: globals may not \
+      be of void type");
+    if let Some(ref init) = init {
+      assert!(init.0.ty == ty.inner(), "pcb_assert: attempt to initialize a \
+        global with a constant of the incorrect type");
+    }
+    Global(ctxt.0.add_global(name, ty.inner(), init.map(|init| init.0), false))
+  }
+
+  // read-only; the type is the type of the initializer
+  pub fn new_constant(ctxt: &'c Ctxt, name: &str, init: Constant<'c>) -> Self {
+    Global(ctxt.0.add_global(name, init.0.ty, Some(init.0), true))
+  }
+
+  pub fn set_linkage(self, linkage: Linkage) {
+    if linkage == Linkage::Common {
+      assert!(self.0.init.is_none() && !self.0.constant, "pcb_assert: only \
+        zero-initialized, non-constant globals may have common linkage");
+    }
+    chk_symbol_attributes(linkage, self.0.visibility.get());
+    self.0.linkage.set(linkage);
+  }
+
+  pub fn set_visibility(self, visibility: Visibility) {
+    chk_symbol_attributes(self.0.linkage.get(), visibility);
+    self.0.visibility.set(visibility);
+  }
+}
+
+fn chk_symbol_attributes(linkage: Linkage, visibility: Visibility) {
+  assert!(!linkage.is_local() || visibility == Visibility::Default,
+    "pcb_assert: symbols with internal or private linkage must have default \
+    visibility");
+}
+
+pub struct Constant<'c>(core::global::Constant<'c>);
+
+impl<'c> Constant<'c> {
+  pub fn int(ty: ty::Type<'c>, value: u64) -> Self {
+    assert!(ty.inner().is_int(), "pcb_assert: attempt to build an integer \
+      constant of non-integer type");
+    Constant(core::global::Constant {
+      ty: ty.inner(),
+      kind: core::global::ConstantKind::Int(value),
+    })
+  }
+
+  pub fn float(ty: ty::Type<'c>, value: f64) -> Self {
+    assert!(ty.inner().is_float(), "pcb_assert: attempt to build a float \
+      constant of non-float type");
+    Constant(core::global::Constant {
+      ty: ty.inner(),
+      kind: core::global::ConstantKind::Float(value),
+    })
+  }
+
+  pub fn bool(ctxt: &'c Ctxt, value: bool) -> Self {
+    Constant(core::global::Constant {
+      ty: ctxt.0.get_type(core::ty::Type::Bool),
+      kind: core::global::ConstantKind::Bool(value),
+    })
+  }
+
+  pub fn aggregate(ty: ty::Type<'c>, fields: Vec<Constant<'c>>) -> Self {
+    assert!(ty.inner().is_aggregate(), "pcb_assert: attempt to build an \
+      aggregate constant of non-aggregate type");
+    let field_tys = ty.inner().aggregate_fields();
+    assert!(fields.len() == field_tys.len(), "pcb_assert: attempt to build an \
+      aggregate constant with the incorrect number of fields");
+    let mut inner_fields = vec![];
+    for (field, field_ty) in fields.into_iter().zip(field_tys.iter()) {
+      assert!(field.0.ty == field_ty, "pcb_assert: attempt to build an \
+        aggregate constant with incorrect field types");
+      inner_fields.push(field.0);
+    }
+    Constant(core::global::Constant {
+      ty: ty.inner(),
+      kind: core::global::ConstantKind::Aggregate(
+        inner_fields.into_boxed_slice()),
+    })
+  }
+
+  pub fn array(ty: ty::Type<'c>, elements: Vec<Constant<'c>>) -> Self {
+    assert!(ty.inner().is_array(), "pcb_assert: attempt to build an array \
+      constant of non-array type");
+    assert!(elements.len() as u64 == ty.inner().array_len(), "pcb_assert: \
+      attempt to build an array constant with the incorrect number of \
+      elements");
+    let mut inner_elements = vec![];
+    for element in elements {
+      assert!(element.0.ty == ty.inner().array_element(), "pcb_assert: \
+        attempt to build an array constant with incorrect element types");
+      inner_elements.push(element.0);
+    }
+    Constant(core::global::Constant {
+      ty: ty.inner(),
+      kind: core::global::ConstantKind::Array(
+        inner_elements.into_boxed_slice()),
+    })
+  }
+
+  // of type [i8; bytes.len()]; not null-terminated
+  pub fn bytes(ctxt: &'c Ctxt, bytes: &[u8]) -> Self {
+    assert!(bytes.len() as u64 <= std::u32::MAX as u64, "pcb_assert: \
+      attempt to build a bytes constant longer than 2^32 - 1 bytes");
+    let ty = core::ty::Type::Array(Box::new(core::ty::Type::Integer(8)),
+      bytes.len() as u64);
+    Constant(core::global::Constant {
+      ty: ctxt.0.get_type(ty),
+      kind: core::global::ConstantKind::Bytes(
+        bytes.to_owned().into_boxed_slice()),
+    })
+  }
+
+  pub fn global_address(ctxt: &'c Ctxt, global: Global<'c>) -> Self {
+    Constant(core::global::Constant {
+      ty: ctxt.0.get_type(core::ty::Type::Pointer),
+      kind: core::global::ConstantKind::GlobalAddress(global.0),
+    })
+  }
+
+  pub fn function_address(ctxt: &'c Ctxt, func: Function<'c>) -> Self {
+    Constant(core::global::Constant {
+      ty: ctxt.0.get_type(func.0.ty.pointer_type()),
+      kind: core::global::ConstantKind::FunctionAddress(func.0),
+    })
+  }
 }
 
 #[derive(Copy, Clone)]
@@ -41,15 +248,94 @@ pub struct Block<'c>(&'c core::function::Block<'c>);
 
 macro_rules! chk_term {
   ($this:expr) => (
-    assert!($this.0.terminator.get().is_none(), "pcb_assert: \
+    chk_no_term!($this);
+    chk_no_tail_call!($this);
+  )
+}
+
+macro_rules! chk_no_term {
+  ($this:expr) => (
+    assert!($this.0.terminator.borrow().is_none(), "pcb_assert: \
       attempt to build instruction after a terminator");
   )
 }
 
+// a tail call may only be followed by a return of its result
+macro_rules! chk_no_tail_call {
+  ($this:expr) => (
+    assert!(!$this.0.block_values.borrow().last()
+      .map_or(false, |v| v.is_tail_call()), "pcb_assert: attempt to build \
+      something other than a return after a tail call");
+  )
+}
+
+macro_rules! chk_not_declaration {
+  ($func:expr) => (
+    assert!(!$func.0.declaration, "pcb_assert: attempt to add a block to the \
+      declaration {}", $func.0.name);
+  )
+}
+
+macro_rules! chk_not_landing_pad {
+  ($blk:expr) => (
+    assert!(!$blk.0.landing_pad, "pcb_assert: attempt to branch to a landing \
+      pad; landing pads may only be reached by unwinding");
+  )
+}
+
+macro_rules! chk_call_args {
+  ($args:expr, $inputs:expr, $variadic:expr) => (
+    if $variadic {
+      assert!($args.len() >= $inputs.len(), "pcb_assert: attempt to call a \
+        variadic function with too few arguments");
+      for arg in &$args[$inputs.len()..] {
+        assert!(*arg.0.ty() != core::ty::Type::Void, "pcb_assert: attempt to \
+          pass a void value as a variadic argument");
+      }
+    } else {
+      assert!($args.len() == $inputs.len(), "pcb_assert: attempt to call a \
+        function with the incorrect number of arguments");
+    }
+  )
+}
+
+// the caller and callee must agree on the calling convention
+macro_rules! chk_call_conv {
+  ($this:expr, $call_conv:expr) => (
+    assert!($call_conv == $this.0.func.ty.call_conv, "pcb_assert: attempt to \
+      call a function with a different calling convention");
+  )
+}
+
+macro_rules! chk_extension {
+  ($value:expr, $ty:expr) => (
+    assert!($ty.inner().is_int(), "pcb_assert: attempt to extend to a \
+      non-integer type");
+    let from_size = match *$value.0.ty() {
+      core::ty::Type::Integer(size) => size,
+      core::ty::Type::Bool => 1,
+      _ => panic!("pcb_assert: attempt to extend a non-integer value"),
+    };
+    assert!($ty.inner().int_size() > from_size, "pcb_assert: attempt to extend \
+      to a type that isn't larger");
+  )
+}
+
+// atomic operations are on integers whose width is a power of two bytes
+macro_rules! chk_atomic_int {
+  ($ty:expr) => (
+    assert!($ty.is_int() && $ty.int_size() >= 8
+      && $ty.int_size().is_power_of_two(), "pcb_assert: atomic operations \
+      must be on integers which are a power of two bytes wide");
+  )
+}
+
 macro_rules! chk_op_types {
   ($lhs:expr, $rhs:expr) => (
     assert!($lhs.0.ty() == $rhs.0.ty(), "pcb_assert: lhs and rhs are not of \
       the same type");
+    assert!(*$lhs.0.ty() != core::ty::Type::Void, "pcb_assert: attempt to \
+      use the result of a call to a void function");
     /*if let core::ty::Type::Integer(_) = *lhs.0.ty() {
     } else {
       panic!("pcb_assert: `add` must take values of integer type");
@@ -57,118 +343,1128 @@ macro_rules! chk_op_types {
   )
 }
 
+macro_rules! chk_int_op_types {
+  ($lhs:expr, $rhs:expr) => (
+    chk_op_types!($lhs, $rhs);
+    assert!($lhs.0.ty().is_int(), "pcb_assert: lhs and rhs are not of \
+      integer type");
+  )
+}
+
+macro_rules! chk_bitwise_op_types {
+  ($lhs:expr, $rhs:expr) => (
+    chk_op_types!($lhs, $rhs);
+    assert!($lhs.0.ty().is_int() || *$lhs.0.ty() == core::ty::Type::Bool,
+      "pcb_assert: lhs and rhs are not of integer or bool type");
+  )
+}
+
+macro_rules! chk_float_op_types {
+  ($lhs:expr, $rhs:expr) => (
+    chk_op_types!($lhs, $rhs);
+    assert!($lhs.0.ty().is_float(), "pcb_assert: lhs and rhs are not of \
+      float type");
+  )
+}
+
 impl<'c> Block<'c> {
   pub fn append(func: Function<'c>) -> Self {
-    Block(func.0.add_block())
+    chk_not_declaration!(func);
+    Block(func.0.add_block(&[]))
+  }
+
+  // the parameters are given their values by the branches to the block. only
+  // build_branch (and build_invoke, for its normal destination) can pass
+  // arguments; cond_branch and switch may only go to blocks without
+  // parameters, so conditional edges into a block with parameters need a
+  // block in between which branches with the arguments. the entry block has
+  // no predecessors, so it may not have parameters
+  pub fn append_with_params(func: Function<'c>, params: &[ty::Type<'c>])
+      -> Self {
+    chk_not_declaration!(func);
+    assert!(params.is_empty() || func.0.blocks.len() != 0, "pcb_assert: \
+      the entry block may not have parameters");
+    let mut inner_params = vec![];
+    for param in params {
+      assert!(*param.inner() != core::ty::Type::Void, "pcb_assert: block \
+        parameters may not be of void type");
+      inner_params.push(param.inner());
+    }
+    Block(func.0.add_block(&inner_params))
+  }
+
+  // a block only reached by unwinding out of an invoke. the catches are the
+  // type info globals of the exceptions to catch, with None catching every
+  // exception; cleanup landing pads are run for every exception
+  pub fn append_landing_pad(func: Function<'c>, cleanup: bool,
+      catches: &[Option<Global<'c>>]) -> Self {
+    chk_not_declaration!(func);
+    assert!(func.0.personality.get().is_some(), "pcb_assert: attempt to add \
+      a landing pad to a function without a personality");
+    assert!(func.0.blocks.len() != 0, "pcb_assert: the entry block may not \
+      be a landing pad");
+    assert!(cleanup || !catches.is_empty(), "pcb_assert: attempt to add a \
+      landing pad that neither cleans up nor catches");
+    let mut inner_catches = vec![];
+    for catch in catches {
+      inner_catches.push(catch.map(|global| global.0));
+    }
+    Block(func.0.add_landing_pad(cleanup, inner_catches.into_boxed_slice()))
+  }
+
+  // the location given to everything built in the block from now on, until
+  // it's set again or cleared
+  pub fn set_location(self, file: SourceFile<'c>, line: u32, column: u32) {
+    self.0.current_loc.set(Some(core::debug::SourceLoc {
+      file: file.0,
+      line: line,
+      column: column,
+    }));
+  }
+  pub fn clear_location(self) {
+    self.0.current_loc.set(None);
+  }
+
+  // the exception a landing pad was reached with; of type (ptr, i32)
+  pub fn get_exception(&self) -> Value<'c> {
+    assert!(self.0.landing_pad, "pcb_assert: attempted to get the exception \
+      of a block which isn't a landing pad");
+    Value(self.0.block_values.borrow()[0])
+  }
+
+  // see Value::set_name
+  pub fn set_name(self, name: &str) {
+    assert!(!name.is_empty(), "pcb_assert: attempt to give a block an empty \
+      name");
+    *self.0.name.borrow_mut() = Some(self.0.func.unique_name(name));
+  }
+
+  pub fn get_parameter(&self, number: u32) -> Value<'c> {
+    assert!(number < self.0.params.len() as u32, "pcb_assert: attempted to \
+      get nonexistent block parameter");
+    Value(self.0.params[number as usize])
   }
 
   pub fn build_const_int(self, ty: ty::Type<'c>, value: u64) -> Value<'c> {
     chk_term!(self);
+    assert!(ty.inner().is_int(), "pcb_assert: attempt to build an integer \
+      constant of non-integer type");
     Value(self.0.add_value(
         core::function::ValueKind::ConstInt { ty: ty.inner(), value: value }))
   }
+  pub fn build_const_float(self, ty: ty::Type<'c>, value: f64) -> Value<'c> {
+    chk_term!(self);
+    assert!(ty.inner().is_float(), "pcb_assert: attempt to build a float \
+      constant of non-float type");
+    Value(self.0.add_value(
+        core::function::ValueKind::ConstFloat { ty: ty.inner(), value: value }))
+  }
+  pub fn build_const_bool(self, value: bool) -> Value<'c> {
+    chk_term!(self);
+    Value(self.0.add_value(core::function::ValueKind::ConstBool(value)))
+  }
+  pub fn build_const_aggregate(self, ty: ty::Type<'c>, fields: &[Value<'c>])
+      -> Value<'c> {
+    chk_term!(self);
+    assert!(ty.inner().is_aggregate(), "pcb_assert: attempt to build an \
+      aggregate constant of non-aggregate type");
+    let field_tys = ty.inner().aggregate_fields();
+    assert!(fields.len() == field_tys.len(), "pcb_assert: attempt to build an \
+      aggregate constant with the incorrect number of fields");
+    let mut inner_fields = vec![];
+    for (field, field_ty) in fields.iter().zip(field_tys.iter()) {
+      assert!(field.0.ty() == field_ty, "pcb_assert: attempt to build an \
+        aggregate constant with incorrect field types");
+      assert!(field.0.is_const(), "pcb_assert: attempt to build an aggregate \
+        constant with non-constant fields");
+      inner_fields.push(field.0);
+    }
+    Value(self.0.add_value(core::function::ValueKind::ConstAggregate {
+      ty: ty.inner(),
+      fields: inner_fields.into_boxed_slice(),
+    }))
+  }
+  pub fn build_const_array(self, ty: ty::Type<'c>, elements: &[Value<'c>])
+      -> Value<'c> {
+    chk_term!(self);
+    assert!(ty.inner().is_array(), "pcb_assert: attempt to build an array \
+      constant of non-array type");
+    assert!(elements.len() as u64 == ty.inner().array_len(), "pcb_assert: \
+      attempt to build an array constant with the incorrect number of \
+      elements");
+    let mut inner_elements = vec![];
+    for element in elements {
+      assert!(element.0.ty() == ty.inner().array_element(), "pcb_assert: \
+        attempt to build an array constant with incorrect element types");
+      assert!(element.0.is_const(), "pcb_assert: attempt to build an array \
+        constant with non-constant elements");
+      inner_elements.push(element.0);
+    }
+    Value(self.0.add_value(core::function::ValueKind::ConstArray {
+      ty: ty.inner(),
+      elements: inner_elements.into_boxed_slice(),
+    }))
+  }
+  // variadic functions may be passed extra arguments after their inputs.
+  // these are passed as-is: C's default argument promotions are not applied,
+  // so when calling a C variadic function, integers narrower than i32 and
+  // bools must be extended to i32, and f32s to f64, by the caller.
+  // the callee must have the same calling convention as the caller
   pub fn build_call(self, func: Function<'c>, args: &[Value<'c>])
       -> Value<'c> {
+    self.call(func, args, false)
+  }
+  // the callee reuses the caller's stack frame, so it must not access the
+  // caller's allocas. its type must be the same as the caller's, and the call
+  // must be followed by a return of its result (or build_return_void)
+  pub fn build_tail_call(self, func: Function<'c>, args: &[Value<'c>])
+      -> Value<'c> {
+    self.call(func, args, true)
+  }
+  fn call(self, func: Function<'c>, args: &[Value<'c>], tail: bool)
+      -> Value<'c> {
     chk_term!(self);
-    assert!(args.len() == func.0.ty.inputs.len(), "pcb_assert: attempt to call \
-      a function with the incorrect number of arguments");
+    chk_call_conv!(self, func.0.ty.call_conv);
+    assert!(!tail || func.0.ty == self.0.func.ty, "pcb_assert: attempt to \
+      tail call a function whose type differs from the caller's");
+    chk_call_args!(args, func.0.ty.inputs, func.0.ty.variadic);
     for (arg, param_ty) in args.iter().zip(func.0.ty.inputs.iter()) {
       assert!(arg.0.ty() == *param_ty, "pcb_assert: attempt to call a function \
         with incorrect argument types");
     }
-    // TODO(ubsan): check calls against type of func
     let mut inner_params = vec![];
     for param in args {
       inner_params.push(param.0)
     }
     Value(self.0.add_value(
       core::function::ValueKind::Call { function: func.0,
-        parameters: inner_params.into_boxed_slice() }))
+        parameters: inner_params.into_boxed_slice(), tail: tail }))
+  }
+  // the calling convention is part of the function pointer type; see
+  // build_call
+  pub fn build_call_indirect(self, callee: Value<'c>, args: &[Value<'c>])
+      -> Value<'c> {
+    self.call_indirect(callee, args, false)
+  }
+  // see build_tail_call
+  pub fn build_tail_call_indirect(self, callee: Value<'c>,
+      args: &[Value<'c>]) -> Value<'c> {
+    self.call_indirect(callee, args, true)
+  }
+  fn call_indirect(self, callee: Value<'c>, args: &[Value<'c>], tail: bool)
+      -> Value<'c> {
+    chk_term!(self);
+    assert!(callee.0.ty().is_fn_ptr(), "pcb_assert: attempt to call a \
+      non-function pointer value");
+    chk_call_conv!(self, callee.0.ty().fn_ptr_call_conv());
+    assert!(!tail || *callee.0.ty() == self.0.func.ty.pointer_type(),
+      "pcb_assert: attempt to tail call a function whose type differs from \
+      the caller's");
+    let inputs = callee.0.ty().fn_ptr_inputs();
+    chk_call_args!(args, inputs, callee.0.ty().fn_ptr_variadic());
+    for (arg, param_ty) in args.iter().zip(inputs.iter()) {
+      assert!(arg.0.ty() == param_ty, "pcb_assert: attempt to call a function \
+        with incorrect argument types");
+    }
+    let mut inner_params = vec![];
+    for arg in args {
+      inner_params.push(arg.0);
+    }
+    Value(self.0.add_value(
+      core::function::ValueKind::CallIndirect { callee: callee.0,
+        parameters: inner_params.into_boxed_slice(), tail: tail }))
+  }
+  // `constraints` is in LLVM's constraint syntax, and isn't checked against
+  // the operands; a void `ty` means the assembly has no outputs.
+  // `side_effects` keeps the assembly from being removed or reordered, and
+  // `align_stack` aligns the stack before it runs
+  pub fn build_inline_asm(self, template: &str, constraints: &str,
+      side_effects: bool, align_stack: bool, ty: ty::Type<'c>,
+      operands: &[Value<'c>]) -> Value<'c> {
+    chk_term!(self);
+    let mut inner_operands = vec![];
+    for operand in operands {
+      assert!(*operand.0.ty() != core::ty::Type::Void, "pcb_assert: attempt \
+        to pass a void operand to inline assembly");
+      inner_operands.push(operand.0);
+    }
+    Value(self.0.add_value(core::function::ValueKind::InlineAsm {
+      template: template.to_owned(),
+      constraints: constraints.to_owned(),
+      side_effects: side_effects,
+      align_stack: align_stack,
+      operands: inner_operands.into_boxed_slice(),
+      ty: ty.inner(),
+    }))
+  }
+  pub fn build_function_address(self, func: Function<'c>) -> Value<'c> {
+    chk_term!(self);
+    Value(self.0.add_value(
+      core::function::ValueKind::FunctionAddress(func.0)))
+  }
+  pub fn build_global_address(self, global: Global<'c>) -> Value<'c> {
+    chk_term!(self);
+    Value(self.0.add_value(core::function::ValueKind::GlobalAddress(global.0)))
+  }
+
+  // -- aggregates --
+  pub fn build_extract_field(self, value: Value<'c>, index: u32)
+      -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_aggregate(), "pcb_assert: attempt to extract a \
+      field from a non-aggregate value");
+    assert!(index < value.0.ty().aggregate_fields().len() as u32,
+      "pcb_assert: attempt to extract a nonexistent field");
+    Value(self.0.add_value(core::function::ValueKind::ExtractField {
+      value: value.0,
+      index: index,
+    }))
+  }
+  pub fn build_insert_field(self, value: Value<'c>, index: u32,
+      field: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_aggregate(), "pcb_assert: attempt to insert a \
+      field into a non-aggregate value");
+    let field_tys = value.0.ty().aggregate_fields();
+    assert!(index < field_tys.len() as u32, "pcb_assert: attempt to insert a \
+      nonexistent field");
+    assert!(*field.0.ty() == field_tys[index as usize], "pcb_assert: attempt \
+      to insert a field of the incorrect type");
+    Value(self.0.add_value(core::function::ValueKind::InsertField {
+      value: value.0,
+      index: index,
+      field: field.0,
+    }))
+  }
+
+  // -- memory --
+  pub fn build_alloca(self, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(*ty.inner() != core::ty::Type::Void, "pcb_assert: attempt to \
+      allocate a value of void type");
+    Value(self.0.add_value(core::function::ValueKind::Alloca(ty.inner())))
+  }
+  pub fn build_load(self, ptr: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      load through a non-pointer value");
+    assert!(*ty.inner() != core::ty::Type::Void, "pcb_assert: attempt to \
+      load a value of void type");
+    Value(self.0.add_value(
+      core::function::ValueKind::Load { ptr: ptr.0, ty: ty.inner() }))
+  }
+  pub fn build_store(self, ptr: Value<'c>, value: Value<'c>) {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      store through a non-pointer value");
+    assert!(*value.0.ty() != core::ty::Type::Void, "pcb_assert: attempt to \
+      use the result of a call to a void function");
+    self.0.add_value(
+      core::function::ValueKind::Store { ptr: ptr.0, value: value.0 });
+  }
+  pub fn build_field_address(self, ptr: Value<'c>, ty: ty::Type<'c>,
+      index: u32) -> Value<'c> {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      get a field address through a non-pointer value");
+    assert!(ty.inner().is_aggregate(), "pcb_assert: attempt to get a field \
+      address of a non-aggregate type");
+    assert!(index < ty.inner().aggregate_fields().len() as u32,
+      "pcb_assert: attempt to get the address of a nonexistent field");
+    Value(self.0.add_value(core::function::ValueKind::FieldAddress {
+      ptr: ptr.0,
+      ty: ty.inner(),
+      index: index,
+    }))
+  }
+  pub fn build_element_address(self, ptr: Value<'c>, ty: ty::Type<'c>,
+      index: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      get an element address through a non-pointer value");
+    assert!(ty.inner().is_array(), "pcb_assert: attempt to get an element \
+      address of a non-array type");
+    assert!(index.0.ty().is_int(), "pcb_assert: attempt to index an array \
+      with a non-integer value");
+    Value(self.0.add_value(core::function::ValueKind::ElementAddress {
+      ptr: ptr.0,
+      ty: ty.inner(),
+      index: index.0,
+    }))
+  }
+
+  // -- atomics --
+  // pointers may also be loaded and stored atomically
+  pub fn build_atomic_load(self, ptr: Value<'c>, ty: ty::Type<'c>,
+      ordering: AtomicOrdering) -> Value<'c> {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      load through a non-pointer value");
+    if *ty.inner() != core::ty::Type::Pointer {
+      chk_atomic_int!(ty.inner());
+    }
+    assert!(!ordering.is_release() || ordering == AtomicOrdering::SeqCst,
+      "pcb_assert: atomic loads may not have release ordering");
+    Value(self.0.add_value(core::function::ValueKind::AtomicLoad {
+      ptr: ptr.0,
+      ty: ty.inner(),
+      ordering: ordering,
+    }))
+  }
+  pub fn build_atomic_store(self, ptr: Value<'c>, value: Value<'c>,
+      ordering: AtomicOrdering) {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      store through a non-pointer value");
+    if *value.0.ty() != core::ty::Type::Pointer {
+      chk_atomic_int!(value.0.ty());
+    }
+    assert!(!ordering.is_acquire() || ordering == AtomicOrdering::SeqCst,
+      "pcb_assert: atomic stores may not have acquire ordering");
+    self.0.add_value(core::function::ValueKind::AtomicStore {
+      ptr: ptr.0,
+      value: value.0,
+      ordering: ordering,
+    });
+  }
+  // returns the value that was previously stored
+  pub fn build_atomic_rmw(self, op: AtomicRmwOp, ptr: Value<'c>,
+      value: Value<'c>, ordering: AtomicOrdering) -> Value<'c> {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      modify through a non-pointer value");
+    chk_atomic_int!(value.0.ty());
+    Value(self.0.add_value(core::function::ValueKind::AtomicRmw {
+      op: op,
+      ptr: ptr.0,
+      value: value.0,
+      ordering: ordering,
+    }))
+  }
+  // returns an aggregate of the value that was previously stored, and whether
+  // `new` was stored. `failure` is the ordering of the load if the value
+  // isn't `expected`; it may not be stronger than `success`
+  pub fn build_cmpxchg(self, ptr: Value<'c>, expected: Value<'c>,
+      new: Value<'c>, success: AtomicOrdering, failure: AtomicOrdering)
+      -> Value<'c> {
+    chk_term!(self);
+    assert!(*ptr.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      compare and exchange through a non-pointer value");
+    chk_op_types!(expected, new);
+    chk_atomic_int!(expected.0.ty());
+    assert!(!failure.is_release() || failure == AtomicOrdering::SeqCst,
+      "pcb_assert: compare and exchange may not have release failure \
+      ordering");
+    assert!((!failure.is_acquire() || success.is_acquire())
+      && (failure != AtomicOrdering::SeqCst
+        || success == AtomicOrdering::SeqCst), "pcb_assert: the failure \
+      ordering of compare and exchange may not be stronger than the success \
+      ordering");
+    Value(self.0.add_value(core::function::ValueKind::CmpXchg {
+      ptr: ptr.0,
+      expected: expected.0,
+      new: new.0,
+      success: success,
+      failure: failure,
+    }))
+  }
+  pub fn build_fence(self, ordering: AtomicOrdering) {
+    chk_term!(self);
+    assert!(ordering != AtomicOrdering::Relaxed, "pcb_assert: fences may not \
+      have relaxed ordering");
+    self.0.add_value(core::function::ValueKind::Fence(ordering));
+  }
+
+  // -- varargs --
+  // returns a pointer to a va_list over the variadic arguments of the current
+  // function; it must be ended with build_va_end before returning
+  pub fn build_va_start(self) -> Value<'c> {
+    chk_term!(self);
+    assert!(self.0.func.ty.variadic, "pcb_assert: attempt to start a va_list \
+      in a non-variadic function");
+    Value(self.0.add_value(core::function::ValueKind::VaStart))
+  }
+  // arguments are read as they were passed; see build_call
+  pub fn build_va_arg(self, list: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(*list.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      read a variadic argument through a non-pointer value");
+    assert!(*ty.inner() != core::ty::Type::Void, "pcb_assert: attempt to read \
+      a variadic argument of void type");
+    Value(self.0.add_value(core::function::ValueKind::VaArg {
+      list: list.0,
+      ty: ty.inner(),
+    }))
+  }
+  pub fn build_va_end(self, list: Value<'c>) {
+    chk_term!(self);
+    assert!(*list.0.ty() == core::ty::Type::Pointer, "pcb_assert: attempt to \
+      end a va_list through a non-pointer value");
+    self.0.add_value(core::function::ValueKind::VaEnd(list.0));
+  }
+
+  // -- unary ops --
+  pub fn build_neg(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int(), "pcb_assert: attempt to negate a \
+      non-integer value");
+    Value(self.0.add_value(core::function::ValueKind::Neg(inner.0)))
+  }
+  pub fn build_not(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int() || *inner.0.ty() == core::ty::Type::Bool,
+      "pcb_assert: attempt to not a value that isn't of integer or bool type");
+    Value(self.0.add_value(core::function::ValueKind::Not(inner.0)))
   }
 
   // -- binops --
+  // the _with_flags and _exact variants make the result undefined if the
+  // flags don't hold, which allows for more optimization
   pub fn build_mul(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    self.build_mul_with_flags(lhs, rhs, WrapFlags::default())
+  }
+  pub fn build_mul_with_flags(self, lhs: Value<'c>, rhs: Value<'c>,
+      flags: WrapFlags) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::Mul(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::Mul(lhs.0, rhs.0, flags)))
   }
   pub fn build_udiv(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::UDiv(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::UDiv(lhs.0, rhs.0, false)))
+  }
+  pub fn build_udiv_exact(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::UDiv(lhs.0, rhs.0, true)))
   }
   pub fn build_sdiv(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::SDiv(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SDiv(lhs.0, rhs.0, false)))
+  }
+  pub fn build_sdiv_exact(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SDiv(lhs.0, rhs.0, true)))
   }
   pub fn build_urem(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
+    chk_int_op_types!(lhs, rhs);
     Value(self.0.add_value(core::function::ValueKind::URem(lhs.0, rhs.0)))
   }
   pub fn build_srem(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
+    chk_int_op_types!(lhs, rhs);
     Value(self.0.add_value(core::function::ValueKind::SRem(lhs.0, rhs.0)))
   }
 
   pub fn build_add(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    self.build_add_with_flags(lhs, rhs, WrapFlags::default())
+  }
+  pub fn build_add_with_flags(self, lhs: Value<'c>, rhs: Value<'c>,
+      flags: WrapFlags) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::Add(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::Add(lhs.0, rhs.0, flags)))
   }
   pub fn build_sub(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    self.build_sub_with_flags(lhs, rhs, WrapFlags::default())
+  }
+  pub fn build_sub_with_flags(self, lhs: Value<'c>, rhs: Value<'c>,
+      flags: WrapFlags) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::Sub(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::Sub(lhs.0, rhs.0, flags)))
   }
 
   pub fn build_shl(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    self.build_shl_with_flags(lhs, rhs, WrapFlags::default())
+  }
+  pub fn build_shl_with_flags(self, lhs: Value<'c>, rhs: Value<'c>,
+      flags: WrapFlags) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::Shl(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::Shl(lhs.0, rhs.0, flags)))
   }
   pub fn build_zshr(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::ZShr(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::ZShr(lhs.0, rhs.0, false)))
+  }
+  pub fn build_zshr_exact(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::ZShr(lhs.0, rhs.0, true)))
   }
   pub fn build_sshr(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
-    Value(self.0.add_value(core::function::ValueKind::SShr(lhs.0, rhs.0)))
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SShr(lhs.0, rhs.0, false)))
+  }
+  pub fn build_sshr_exact(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SShr(lhs.0, rhs.0, true)))
   }
 
   pub fn build_and(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
+    chk_bitwise_op_types!(lhs, rhs);
     Value(self.0.add_value(core::function::ValueKind::And(lhs.0, rhs.0)))
   }
   pub fn build_xor(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
+    chk_bitwise_op_types!(lhs, rhs);
     Value(self.0.add_value(core::function::ValueKind::Xor(lhs.0, rhs.0)))
   }
   pub fn build_or(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
     chk_term!(self);
-    chk_op_types!(lhs, rhs);
+    chk_bitwise_op_types!(lhs, rhs);
     Value(self.0.add_value(core::function::ValueKind::Or(lhs.0, rhs.0)))
   }
 
+  // -- checked arithmetic --
+  // returns an aggregate of the wrapped result and whether it overflowed
+  pub fn build_sadd_overflow(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SAddOverflow(lhs.0, rhs.0)))
+  }
+  pub fn build_uadd_overflow(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::UAddOverflow(lhs.0, rhs.0)))
+  }
+  pub fn build_ssub_overflow(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SSubOverflow(lhs.0, rhs.0)))
+  }
+  pub fn build_usub_overflow(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::USubOverflow(lhs.0, rhs.0)))
+  }
+  pub fn build_smul_overflow(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SMulOverflow(lhs.0, rhs.0)))
+  }
+  pub fn build_umul_overflow(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::UMulOverflow(lhs.0, rhs.0)))
+  }
+  // clamps the result to the range of the type
+  pub fn build_sadd_sat(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SAddSat(lhs.0, rhs.0)))
+  }
+  pub fn build_uadd_sat(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::UAddSat(lhs.0, rhs.0)))
+  }
+  pub fn build_ssub_sat(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::SSubSat(lhs.0, rhs.0)))
+  }
+  pub fn build_usub_sat(self, lhs: Value<'c>, rhs: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(
+      core::function::ValueKind::USubSat(lhs.0, rhs.0)))
+  }
+
+  // -- bit manipulation --
+  pub fn build_ctpop(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int(), "pcb_assert: attempt to count the bits of a \
+      non-integer value");
+    Value(self.0.add_value(core::function::ValueKind::Ctpop(inner.0)))
+  }
+  pub fn build_ctlz(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int(), "pcb_assert: attempt to \
+      count the leading zeros of a non-integer value");
+    Value(self.0.add_value(core::function::ValueKind::Ctlz(inner.0)))
+  }
+  pub fn build_cttz(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int(), "pcb_assert: attempt to \
+      count the trailing zeros of a non-integer value");
+    Value(self.0.add_value(core::function::ValueKind::Cttz(inner.0)))
+  }
+  pub fn build_bswap(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int(), "pcb_assert: attempt to byte swap a \
+      non-integer value");
+    assert!(inner.0.ty().int_size() % 16 == 0, "pcb_assert: attempt to \
+      byte swap a value whose width isn't a multiple of 16 bits");
+    Value(self.0.add_value(core::function::ValueKind::Bswap(inner.0)))
+  }
+  pub fn build_bitreverse(self, inner: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(inner.0.ty().is_int(), "pcb_assert: attempt to \
+      reverse the bits of a non-integer value");
+    Value(self.0.add_value(core::function::ValueKind::BitReverse(inner.0)))
+  }
+  // `hi` and `lo` are concatenated and shifted left by `amount` modulo the
+  // width; the high half is returned
+  pub fn build_fshl(self, hi: Value<'c>, lo: Value<'c>, amount: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(hi, lo);
+    chk_int_op_types!(hi, amount);
+    Value(self.0.add_value(core::function::ValueKind::Fshl {
+      hi: hi.0,
+      lo: lo.0,
+      amount: amount.0,
+    }))
+  }
+  // `hi` and `lo` are concatenated and shifted right by `amount` modulo the
+  // width; the low half is returned
+  pub fn build_fshr(self, hi: Value<'c>, lo: Value<'c>, amount: Value<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(hi, lo);
+    chk_int_op_types!(hi, amount);
+    Value(self.0.add_value(core::function::ValueKind::Fshr {
+      hi: hi.0,
+      lo: lo.0,
+      amount: amount.0,
+    }))
+  }
+  pub fn build_rotl(self, value: Value<'c>, amount: Value<'c>) -> Value<'c> {
+    self.build_fshl(value, value, amount)
+  }
+  pub fn build_rotr(self, value: Value<'c>, amount: Value<'c>) -> Value<'c> {
+    self.build_fshr(value, value, amount)
+  }
+
+  // -- comparisons --
+  pub fn build_eq(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_op_types!(lhs, rhs);
+    assert!(lhs.0.ty().is_int() || *lhs.0.ty() == core::ty::Type::Bool
+      || *lhs.0.ty() == core::ty::Type::Pointer, "pcb_assert: lhs and rhs are \
+      not of integer, bool or pointer type");
+    Value(self.0.add_value(core::function::ValueKind::Eq(lhs.0, rhs.0)))
+  }
+  pub fn build_neq(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_op_types!(lhs, rhs);
+    assert!(lhs.0.ty().is_int() || *lhs.0.ty() == core::ty::Type::Bool
+      || *lhs.0.ty() == core::ty::Type::Pointer, "pcb_assert: lhs and rhs are \
+      not of integer, bool or pointer type");
+    Value(self.0.add_value(core::function::ValueKind::Neq(lhs.0, rhs.0)))
+  }
+
+  pub fn build_ult(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::ULt(lhs.0, rhs.0)))
+  }
+  pub fn build_slt(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::SLt(lhs.0, rhs.0)))
+  }
+
+  pub fn build_ugt(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::UGt(lhs.0, rhs.0)))
+  }
+  pub fn build_sgt(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::SGt(lhs.0, rhs.0)))
+  }
+
+  pub fn build_ulte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::ULte(lhs.0, rhs.0)))
+  }
+  pub fn build_slte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::SLte(lhs.0, rhs.0)))
+  }
+
+  pub fn build_ugte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::UGte(lhs.0, rhs.0)))
+  }
+  pub fn build_sgte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_int_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::SGte(lhs.0, rhs.0)))
+  }
+
+  // -- float binops --
+  pub fn build_fadd(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FAdd(lhs.0, rhs.0)))
+  }
+  pub fn build_fsub(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FSub(lhs.0, rhs.0)))
+  }
+  pub fn build_fmul(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FMul(lhs.0, rhs.0)))
+  }
+  pub fn build_fdiv(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FDiv(lhs.0, rhs.0)))
+  }
+  pub fn build_frem(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FRem(lhs.0, rhs.0)))
+  }
+
+  // -- float comparisons; o is ordered, u is unordered --
+  pub fn build_foeq(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FOEq(lhs.0, rhs.0)))
+  }
+  pub fn build_foneq(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FONeq(lhs.0, rhs.0)))
+  }
+  pub fn build_folt(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FOLt(lhs.0, rhs.0)))
+  }
+  pub fn build_fogt(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FOGt(lhs.0, rhs.0)))
+  }
+  pub fn build_folte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FOLte(lhs.0, rhs.0)))
+  }
+  pub fn build_fogte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FOGte(lhs.0, rhs.0)))
+  }
+
+  pub fn build_fueq(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FUEq(lhs.0, rhs.0)))
+  }
+  pub fn build_funeq(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FUNeq(lhs.0, rhs.0)))
+  }
+  pub fn build_fult(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FULt(lhs.0, rhs.0)))
+  }
+  pub fn build_fugt(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FUGt(lhs.0, rhs.0)))
+  }
+  pub fn build_fulte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FULte(lhs.0, rhs.0)))
+  }
+  pub fn build_fugte(self, lhs: Value<'c>, rhs: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_float_op_types!(lhs, rhs);
+    Value(self.0.add_value(core::function::ValueKind::FUGte(lhs.0, rhs.0)))
+  }
+
+  // -- int <-> float conversions --
+  pub fn build_ftoui(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_float(), "pcb_assert: attempt to convert a \
+      non-float value");
+    assert!(ty.inner().is_int(), "pcb_assert: attempt to convert to a \
+      non-integer type");
+    Value(self.0.add_value(
+      core::function::ValueKind::FToUI { value: value.0, ty: ty.inner() }))
+  }
+  pub fn build_ftosi(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_float(), "pcb_assert: attempt to convert a \
+      non-float value");
+    assert!(ty.inner().is_int(), "pcb_assert: attempt to convert to a \
+      non-integer type");
+    Value(self.0.add_value(
+      core::function::ValueKind::FToSI { value: value.0, ty: ty.inner() }))
+  }
+  pub fn build_uitof(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_int(), "pcb_assert: attempt to convert a \
+      non-integer value");
+    assert!(ty.inner().is_float(), "pcb_assert: attempt to convert to a \
+      non-float type");
+    Value(self.0.add_value(
+      core::function::ValueKind::UIToF { value: value.0, ty: ty.inner() }))
+  }
+  pub fn build_sitof(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_int(), "pcb_assert: attempt to convert a \
+      non-integer value");
+    assert!(ty.inner().is_float(), "pcb_assert: attempt to convert to a \
+      non-float type");
+    Value(self.0.add_value(
+      core::function::ValueKind::SIToF { value: value.0, ty: ty.inner() }))
+  }
+
+  // -- width conversions --
+  pub fn build_trunc(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(value.0.ty().is_int() && ty.inner().is_int(), "pcb_assert: \
+      attempt to truncate a non-integer value or to a non-integer type");
+    assert!(ty.inner().int_size() < value.0.ty().int_size(), "pcb_assert: \
+      attempt to truncate to a type that isn't smaller");
+    Value(self.0.add_value(
+      core::function::ValueKind::Trunc { value: value.0, ty: ty.inner() }))
+  }
+  // bools may be extended; true zero-extends to 1
+  pub fn build_zext(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_extension!(value, ty);
+    Value(self.0.add_value(
+      core::function::ValueKind::ZExt { value: value.0, ty: ty.inner() }))
+  }
+  // bools may be extended; true sign-extends to -1
+  pub fn build_sext(self, value: Value<'c>, ty: ty::Type<'c>) -> Value<'c> {
+    chk_term!(self);
+    chk_extension!(value, ty);
+    Value(self.0.add_value(
+      core::function::ValueKind::SExt { value: value.0, ty: ty.inner() }))
+  }
+  // between integer and float types of the same size
+  pub fn build_bitcast(self, value: Value<'c>, ty: ty::Type<'c>)
+      -> Value<'c> {
+    chk_term!(self);
+    fn size(ty: &core::ty::Type) -> u32 {
+      match *ty {
+        core::ty::Type::Integer(size) | core::ty::Type::Float(size) => size,
+        _ => panic!("pcb_assert: attempt to bitcast a non-integer, non-float \
+          value or to a non-integer, non-float type"),
+      }
+    }
+    assert!(size(value.0.ty()) == size(ty.inner()), "pcb_assert: attempt to \
+      bitcast to a type of a different size");
+    Value(self.0.add_value(
+      core::function::ValueKind::Bitcast { value: value.0, ty: ty.inner() }))
+  }
+
+  // -- misc --
+  pub fn build_select(self, cond: Value<'c>, then: Value<'c>,
+      else_: Value<'c>) -> Value<'c> {
+    chk_term!(self);
+    assert!(*cond.0.ty() == core::ty::Type::Bool, "pcb_assert: attempt to \
+      select on a non-bool value");
+    chk_op_types!(then, else_);
+    Value(self.0.add_value(core::function::ValueKind::Select {
+      cond: cond.0,
+      then: then.0,
+      else_: else_.0,
+    }))
+  }
+
   pub fn build_return(self, value: Value<'c>) {
+    chk_no_term!(self);
+    if let Some(last) = self.0.block_values.borrow().last() {
+      assert!(!last.is_tail_call() || last.number == value.0.number,
+        "pcb_assert: attempt to return something other than the result of a \
+        tail call");
+    }
+    assert!(*value.0.ty() != core::ty::Type::Void, "pcb_assert: attempt to \
+      use the result of a call to a void function");
+    assert!(value.0.ty() == self.0.func.ty.output, "pcb_assert: attempt to \
+      return a value of the incorrect type");
+    self.0.set_terminator(core::function::Terminator::Return(value.0));
+  }
+  pub fn build_return_void(self) {
+    chk_no_term!(self);
+    assert!(*self.0.func.ty.output == core::ty::Type::Void, "pcb_assert: \
+      attempt to return void from a non-void function");
+    self.0.set_terminator(core::function::Terminator::ReturnVoid);
+  }
+  pub fn build_unreachable(self) {
+    chk_term!(self);
+    self.0.set_terminator(core::function::Terminator::Unreachable);
+  }
+  // `normal` takes the result of the call as its only parameter, or takes no
+  // parameters if the function returns void
+  pub fn build_invoke(self, func: Function<'c>, args: &[Value<'c>],
+      normal: Block<'c>, unwind: Block<'c>) {
+    chk_term!(self);
+    chk_call_conv!(self, func.0.ty.call_conv);
+    chk_call_args!(args, func.0.ty.inputs, func.0.ty.variadic);
+    for (arg, param_ty) in args.iter().zip(func.0.ty.inputs.iter()) {
+      assert!(arg.0.ty() == *param_ty, "pcb_assert: attempt to call a function \
+        with incorrect argument types");
+    }
+    chk_not_landing_pad!(normal);
+    assert!(unwind.0.landing_pad, "pcb_assert: attempt to unwind to a block \
+      which isn't a landing pad");
+    if *func.0.ty.output == core::ty::Type::Void {
+      assert!(normal.0.params.is_empty(), "pcb_assert: the normal \
+        destination of an invoke of a void function may not have parameters");
+    } else {
+      assert!(normal.0.params.len() == 1
+        && normal.0.params[0].ty() == func.0.ty.output, "pcb_assert: the \
+        normal destination of an invoke must take the result as its only \
+        parameter");
+    }
+    let mut inner_args = vec![];
+    for arg in args {
+      inner_args.push(arg.0);
+    }
+    self.0.set_terminator(core::function::Terminator::Invoke {
+      function: func.0,
+      parameters: inner_args.into_boxed_slice(),
+      normal: normal.0,
+      unwind: unwind.0,
+    });
+  }
+  pub fn build_resume(self, exception: Value<'c>) {
+    chk_term!(self);
+    assert!(*exception.0.ty() == core::ty::Type::exception(), "pcb_assert: \
+      attempt to resume a value which isn't an exception");
+    assert!(self.0.func.personality.get().is_some(), "pcb_assert: attempt \
+      to resume in a function without a personality");
+    self.0.set_terminator(core::function::Terminator::Resume(exception.0));
+  }
+  pub fn build_branch(self, blk: Block<'c>, args: &[Value<'c>]) {
+    chk_term!(self);
+    chk_not_landing_pad!(blk);
+    assert!(args.len() == blk.0.params.len(), "pcb_assert: attempt to branch \
+      to a block with the incorrect number of arguments");
+    let mut inner_args = vec![];
+    for (arg, param) in args.iter().zip(blk.0.params.iter()) {
+      assert!(arg.0.ty() == param.ty(), "pcb_assert: attempt to branch to a \
+        block with incorrect argument types");
+      inner_args.push(arg.0);
+    }
+    self.0.set_terminator(
+      core::function::Terminator::Branch(blk.0, inner_args.into_boxed_slice()));
+  }
+  pub fn build_cond_branch(self, cond: Value<'c>, then: Block<'c>,
+      else_: Block<'c>) {
     chk_term!(self);
-    self.0.terminator.set(core::function::Terminator::Return(value.0))
+    assert!(*cond.0.ty() == core::ty::Type::Bool, "pcb_assert: attempt to \
+      branch on a non-bool condition");
+    chk_not_landing_pad!(then);
+    chk_not_landing_pad!(else_);
+    assert!(then.0.params.is_empty() && else_.0.params.is_empty(),
+      "pcb_assert: attempt to conditionally branch to a block with \
+      parameters");
+    self.0.set_terminator(core::function::Terminator::CondBranch {
+      cond: cond.0,
+      then: then.0,
+      else_: else_.0,
+    });
   }
-  pub fn build_branch(self, blk: Block<'c>) {
+  pub fn build_switch(self, value: Value<'c>, default: Block<'c>,
+      cases: &[(u64, Block<'c>)]) {
     chk_term!(self);
-    self.0.terminator.set(core::function::Terminator::Branch(blk.0))
+    assert!(value.0.ty().is_int(), "pcb_assert: attempt to switch on a \
+      non-integer value");
+    let size = value.0.ty().int_size();
+    let mut seen = std::collections::HashSet::new();
+    let mut inner_cases = vec![];
+    assert!(default.0.params.is_empty(), "pcb_assert: attempt to switch to a \
+      block with parameters");
+    chk_not_landing_pad!(default);
+    for &(n, blk) in cases {
+      chk_not_landing_pad!(blk);
+      assert!(blk.0.params.is_empty(), "pcb_assert: attempt to switch to a \
+        block with parameters");
+      assert!(size >= 64 || n >> size == 0, "pcb_assert: switch case value \
+        does not fit in the type of the switched-on value");
+      assert!(seen.insert(n), "pcb_assert: duplicate switch case value");
+      inner_cases.push((n, blk.0));
+    }
+    self.0.set_terminator(core::function::Terminator::Switch {
+      value: value.0,
+      default: default.0,
+      cases: inner_cases.into_boxed_slice(),
+    });
   }
 }
 
 #[derive(Copy, Clone)]
 pub struct Value<'c>(&'c core::function::Value<'c>);
 
+impl<'c> Value<'c> {
+  // used when printing, and as the name of the value in the backend; made
+  // unique within the function by adding a suffix if need be. void values
+  // can't be named
+  pub fn set_name(self, name: &str) {
+    assert!(!name.is_empty(), "pcb_assert: attempt to give a value an empty \
+      name");
+    assert!(*self.0.ty() != core::ty::Type::Void, "pcb_assert: attempt to \
+      name a void value");
+    *self.0.name.borrow_mut() = Some(self.0.func.unique_name(name));
+  }
+}
+
 trait TyExt {
   type Output;
   fn inner(self) -> Self::Output;
@@ -178,6 +1474,7 @@ trait TyExt {
 pub mod ty {
   use core::ty;
   use super::Ctxt;
+  pub use core::ty::CallConv;
   #[derive(Copy, Clone, PartialEq, Eq, Hash)]
   pub struct Type<'c>(&'c ty::Type);
 
@@ -185,6 +1482,48 @@ pub mod ty {
     pub fn int(ctxt: &Ctxt, size: u32) -> Type {
       Type(ctxt.0.get_type(ty::Type::Integer(size)))
     }
+
+    pub fn float(ctxt: &Ctxt, size: u32) -> Type {
+      assert!(size == 32 || size == 64, "pcb_assert: floats must be 32 or 64 \
+        bits");
+      Type(ctxt.0.get_type(ty::Type::Float(size)))
+    }
+
+    pub fn bool(ctxt: &Ctxt) -> Type {
+      Type(ctxt.0.get_type(ty::Type::Bool))
+    }
+
+    pub fn void(ctxt: &Ctxt) -> Type {
+      Type(ctxt.0.get_type(ty::Type::Void))
+    }
+
+    pub fn pointer(ctxt: &Ctxt) -> Type {
+      Type(ctxt.0.get_type(ty::Type::Pointer))
+    }
+
+    pub fn function_pointer(ctxt: &'c Ctxt, func: &Function<'c>)
+        -> Type<'c> {
+      Type(ctxt.0.get_type(func.0.pointer_type()))
+    }
+
+    pub fn aggregate(ctxt: &'c Ctxt, fields: &[Type<'c>]) -> Type<'c> {
+      let mut inner_fields = vec![];
+      for field in fields {
+        assert!(*field.0 != ty::Type::Void, "pcb_assert: aggregate fields may \
+          not be of void type");
+        inner_fields.push(field.0.clone());
+      }
+      Type(ctxt.0.get_type(ty::Type::Aggregate(inner_fields)))
+    }
+
+    // `len` may be at most 2^32 - 1, as that's what LLVM allows
+    pub fn array(ctxt: &'c Ctxt, element: Type<'c>, len: u64) -> Type<'c> {
+      assert!(*element.0 != ty::Type::Void, "pcb_assert: array elements may \
+        not be of void type");
+      assert!(len <= ::std::u32::MAX as u64, "pcb_assert: attempt to build \
+        an array type longer than 2^32 - 1 elements");
+      Type(ctxt.0.get_type(ty::Type::Array(Box::new(element.0.clone()), len)))
+    }
   }
 
   #[derive(Clone)]
@@ -194,14 +1533,33 @@ pub mod ty {
     pub fn new(inputs: Vec<Type<'c>>, output: Type<'c>) -> Function<'c> {
       let mut input_inner = vec![];
       for input in inputs {
+        assert!(*input.0 != ty::Type::Void, "pcb_assert: function parameters \
+          may not be of void type");
         input_inner.push(input.0);
       }
       Function(ty::Function {
         inputs: input_inner.into_boxed_slice(),
         output: output.0,
+        variadic: false,
+        call_conv: CallConv::C,
       })
     }
 
+    // a function taking any number of extra arguments after `inputs`
+    pub fn new_variadic(inputs: Vec<Type<'c>>, output: Type<'c>)
+        -> Function<'c> {
+      let mut ret = Self::new(inputs, output);
+      ret.0.variadic = true;
+      ret
+    }
+
+    // only the C calling convention supports variadic functions
+    pub fn set_call_conv(&mut self, call_conv: CallConv) {
+      assert!(call_conv == CallConv::C || !self.0.variadic, "pcb_assert: \
+        variadic functions must use the C calling convention");
+      self.0.call_conv = call_conv;
+    }
+
     #[inline(always)]
     pub fn output(&self) -> Type<'c> {
       Type(self.0.output)
@@ -219,3 +1577,411 @@ pub mod ty {
     fn inner_ref(&self) -> &ty::Function<'c> { &self.0 }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{AtomicOrdering, Block, Ctxt, Function, Global};
+  use super::ty::{self, Type};
+
+  // f(i32) -> i32
+  fn function<'c>(ctxt: &'c Ctxt, name: &str) -> Function<'c> {
+    let i32_ty = Type::int(ctxt, 32);
+    Function::new(ctxt, name, ty::Function::new(vec![i32_ty], i32_ty))
+  }
+
+  // gives `func` a declared personality, which is returned
+  fn with_personality<'c>(ctxt: &'c Ctxt, func: Function<'c>) -> Function<'c> {
+    let i32_ty = Type::int(ctxt, 32);
+    let personality = Function::declare(ctxt, "personality",
+      ty::Function::new_variadic(vec![], i32_ty));
+    func.set_personality(personality);
+    personality
+  }
+
+  // -- switch --
+  #[test]
+  fn switch_prints_cases() {
+    let ctxt = Ctxt::new();
+    {
+      let f = function(&ctxt, "f");
+      let entry = Block::append(f);
+      let one = Block::append(f);
+      let other = Block::append(f);
+      entry.build_switch(f.get_argument(0), other, &[(1, one), (2, one)]);
+      one.build_return(f.get_argument(0));
+      other.build_return(f.get_argument(0));
+    }
+    assert!(ctxt.to_string().contains("switch %0 bb2 [1: bb1, 2: bb1]"));
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: duplicate switch case value")]
+  fn switch_rejects_duplicate_cases() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let other = Block::append(f);
+    entry.build_switch(f.get_argument(0), other, &[(1, other), (1, other)]);
+  }
+
+  #[test]
+  fn switch_allows_cases_of_full_width() {
+    let ctxt = Ctxt::new();
+    let i8_ty = Type::int(&ctxt, 8);
+    let f = Function::new(&ctxt, "f", ty::Function::new(vec![i8_ty], i8_ty));
+    let entry = Block::append(f);
+    let other = Block::append(f);
+    entry.build_switch(f.get_argument(0), other, &[(255, other)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: switch case value does not fit")]
+  fn switch_rejects_cases_wider_than_the_value() {
+    let ctxt = Ctxt::new();
+    let i8_ty = Type::int(&ctxt, 8);
+    let f = Function::new(&ctxt, "f", ty::Function::new(vec![i8_ty], i8_ty));
+    let entry = Block::append(f);
+    let other = Block::append(f);
+    entry.build_switch(f.get_argument(0), other, &[(256, other)]);
+  }
+
+  // -- block parameters --
+  #[test]
+  fn branch_passes_arguments() {
+    let ctxt = Ctxt::new();
+    {
+      let i32_ty = Type::int(&ctxt, 32);
+      let f = function(&ctxt, "f");
+      let entry = Block::append(f);
+      let exit = Block::append_with_params(f, &[i32_ty]);
+      entry.build_branch(exit, &[f.get_argument(0)]);
+      exit.build_return(exit.get_parameter(0));
+    }
+    assert_eq!(ctxt.to_string(), "define f(i32) -> i32 {\n\
+      bb0:\n\
+      \x20 branch bb1(%0)\n\
+      bb1(%1: i32):\n\
+      \x20 return %1\n\
+      }\n");
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: the entry block may not have \
+    parameters")]
+  fn entry_block_rejects_parameters() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    Block::append_with_params(f, &[Type::int(&ctxt, 32)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to branch to a block with \
+    the incorrect number of arguments")]
+  fn branch_rejects_wrong_arity() {
+    let ctxt = Ctxt::new();
+    let i32_ty = Type::int(&ctxt, 32);
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let exit = Block::append_with_params(f, &[i32_ty, i32_ty]);
+    entry.build_branch(exit, &[f.get_argument(0)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to branch to a block with \
+    incorrect argument types")]
+  fn branch_rejects_wrong_types() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let exit = Block::append_with_params(f, &[Type::bool(&ctxt)]);
+    entry.build_branch(exit, &[f.get_argument(0)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to conditionally branch to \
+    a block with parameters")]
+  fn cond_branch_rejects_blocks_with_parameters() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let exit = Block::append_with_params(f, &[Type::int(&ctxt, 32)]);
+    let cond = entry.build_const_bool(true);
+    entry.build_cond_branch(cond, exit, exit);
+  }
+
+  // -- atomics --
+  fn cmpxchg(success: AtomicOrdering, failure: AtomicOrdering) {
+    let ctxt = Ctxt::new();
+    let i32_ty = Type::int(&ctxt, 32);
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let ptr = entry.build_alloca(i32_ty);
+    let arg = f.get_argument(0);
+    entry.build_cmpxchg(ptr, arg, arg, success, failure);
+  }
+
+  #[test]
+  fn cmpxchg_allows_valid_orderings() {
+    cmpxchg(AtomicOrdering::Relaxed, AtomicOrdering::Relaxed);
+    cmpxchg(AtomicOrdering::Release, AtomicOrdering::Relaxed);
+    cmpxchg(AtomicOrdering::AcqRel, AtomicOrdering::Acquire);
+    cmpxchg(AtomicOrdering::SeqCst, AtomicOrdering::SeqCst);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: compare and exchange may not have \
+    release failure ordering")]
+  fn cmpxchg_rejects_release_failure() {
+    cmpxchg(AtomicOrdering::SeqCst, AtomicOrdering::Release);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: compare and exchange may not have \
+    release failure ordering")]
+  fn cmpxchg_rejects_acq_rel_failure() {
+    cmpxchg(AtomicOrdering::AcqRel, AtomicOrdering::AcqRel);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: the failure ordering of compare and \
+    exchange may not be stronger than the success ordering")]
+  fn cmpxchg_rejects_failure_stronger_than_success() {
+    cmpxchg(AtomicOrdering::Release, AtomicOrdering::Acquire);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: atomic loads may not have release \
+    ordering")]
+  fn atomic_load_rejects_release() {
+    let ctxt = Ctxt::new();
+    let i32_ty = Type::int(&ctxt, 32);
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let ptr = entry.build_alloca(i32_ty);
+    entry.build_atomic_load(ptr, i32_ty, AtomicOrdering::SeqCst);
+    entry.build_atomic_load(ptr, i32_ty, AtomicOrdering::Release);
+  }
+
+  // -- exceptions --
+  #[test]
+  fn invoke_prints_edges() {
+    let ctxt = Ctxt::new();
+    {
+      let i32_ty = Type::int(&ctxt, 32);
+      let f = function(&ctxt, "f");
+      with_personality(&ctxt, f);
+      let type_info = Global::new(&ctxt, "type_info", Type::pointer(&ctxt),
+        None);
+      let entry = Block::append(f);
+      let normal = Block::append_with_params(f, &[i32_ty]);
+      let pad = Block::append_landing_pad(f, true, &[Some(type_info), None]);
+      entry.build_invoke(f, &[f.get_argument(0)], normal, pad);
+      normal.build_return(normal.get_parameter(0));
+      pad.build_resume(pad.get_exception());
+    }
+    let printed = ctxt.to_string();
+    assert!(printed.contains("personality personality {"));
+    assert!(printed.contains("invoke f(%0) bb1 unwind bb2"));
+    assert!(printed.contains("resume %"));
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to add a landing pad to a \
+    function without a personality")]
+  fn landing_pad_requires_personality() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    Block::append(f);
+    Block::append_landing_pad(f, true, &[]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: the entry block may not be a \
+    landing pad")]
+  fn entry_block_rejects_landing_pad() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    with_personality(&ctxt, f);
+    Block::append_landing_pad(f, true, &[]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to branch to a landing pad")]
+  fn branch_rejects_landing_pad() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    with_personality(&ctxt, f);
+    let entry = Block::append(f);
+    let pad = Block::append_landing_pad(f, true, &[]);
+    entry.build_branch(pad, &[]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to unwind to a block which \
+    isn't a landing pad")]
+  fn invoke_rejects_unwinding_to_ordinary_block() {
+    let ctxt = Ctxt::new();
+    let i32_ty = Type::int(&ctxt, 32);
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let normal = Block::append_with_params(f, &[i32_ty]);
+    let other = Block::append(f);
+    entry.build_invoke(f, &[f.get_argument(0)], normal, other);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: the normal destination of an invoke \
+    must take the result as its only parameter")]
+  fn invoke_requires_result_parameter() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    with_personality(&ctxt, f);
+    let entry = Block::append(f);
+    let normal = Block::append(f);
+    let pad = Block::append_landing_pad(f, true, &[]);
+    entry.build_invoke(f, &[f.get_argument(0)], normal, pad);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to change the personality \
+    of f after adding landing pads")]
+  fn personality_is_fixed_by_landing_pads() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let personality = with_personality(&ctxt, f);
+    Block::append(f);
+    Block::append_landing_pad(f, true, &[]);
+    f.set_personality(personality);
+    f.set_personality(function(&ctxt, "other"));
+  }
+
+  // -- tail calls --
+  #[test]
+  fn tail_call_returns_its_result() {
+    let ctxt = Ctxt::new();
+    {
+      let f = function(&ctxt, "f");
+      let entry = Block::append(f);
+      let result = entry.build_tail_call(f, &[f.get_argument(0)]);
+      entry.build_return(result);
+    }
+    assert!(ctxt.to_string().contains("= tail call f(%0)\n  return %1\n"));
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to tail call a function \
+    whose type differs from the caller's")]
+  fn tail_call_requires_same_type() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let g = Function::new(&ctxt, "g",
+      ty::Function::new(vec![], Type::int(&ctxt, 32)));
+    let entry = Block::append(f);
+    entry.build_tail_call(g, &[]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to build something other \
+    than a return after a tail call")]
+  fn tail_call_must_be_followed_by_return() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    let result = entry.build_tail_call(f, &[f.get_argument(0)]);
+    entry.build_add(result, result);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to return something other \
+    than the result of a tail call")]
+  fn tail_call_must_return_its_result() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let entry = Block::append(f);
+    entry.build_tail_call(f, &[f.get_argument(0)]);
+    entry.build_return(f.get_argument(0));
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to call a function with a \
+    different calling convention")]
+  fn call_requires_same_calling_convention() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let mut cold_ty = ty::Function::new(vec![], Type::int(&ctxt, 32));
+    cold_ty.set_call_conv(ty::CallConv::Cold);
+    let cold = Function::declare(&ctxt, "cold", cold_ty);
+    let entry = Block::append(f);
+    entry.build_call(cold, &[]);
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to call a function with a \
+    different calling convention")]
+  fn invoke_requires_same_calling_convention() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    with_personality(&ctxt, f);
+    let mut cold_ty = ty::Function::new(vec![], Type::void(&ctxt));
+    cold_ty.set_call_conv(ty::CallConv::Cold);
+    let cold = Function::declare(&ctxt, "cold", cold_ty);
+    let entry = Block::append(f);
+    let normal = Block::append(f);
+    let pad = Block::append_landing_pad(f, true, &[]);
+    entry.build_invoke(cold, &[], normal, pad);
+  }
+
+  // -- names --
+  #[test]
+  fn names_are_unique() {
+    let ctxt = Ctxt::new();
+    {
+      let f = function(&ctxt, "f");
+      let entry = Block::append(f);
+      entry.set_name("x");
+      let arg = f.get_argument(0);
+      arg.set_name("x");
+      let sum = entry.build_add(arg, arg);
+      sum.set_name("x");
+      let product = entry.build_mul(sum, sum);
+      product.set_name("bb3");
+      entry.build_return(product);
+    }
+    assert_eq!(ctxt.to_string(), "define f(i32) -> i32 {\n\
+      x:\n\
+      \x20 %x.2: i32 = add %x.1 %x.1\n\
+      \x20 %bb3.1: i32 = mul %x.2 %x.2\n\
+      \x20 return %bb3.1\n\
+      }\n");
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to name a void value")]
+  fn void_values_cannot_be_named() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    let g = Function::declare(&ctxt, "g",
+      ty::Function::new(vec![], Type::void(&ctxt)));
+    let entry = Block::append(f);
+    entry.build_call(g, &[]).set_name("nothing");
+  }
+
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to give a value an empty \
+    name")]
+  fn names_cannot_be_empty() {
+    let ctxt = Ctxt::new();
+    let f = function(&ctxt, "f");
+    f.get_argument(0).set_name("");
+  }
+
+  // -- types --
+  #[test]
+  #[should_panic(expected = "pcb_assert: attempt to build an array type \
+    longer than 2^32 - 1 elements")]
+  fn array_length_fits_in_u32() {
+    let ctxt = Ctxt::new();
+    Type::array(&ctxt, Type::int(&ctxt, 8), ::std::u32::MAX as u64);
+    Type::array(&ctxt, Type::int(&ctxt, 8), ::std::u32::MAX as u64 + 1);
+  }
+}